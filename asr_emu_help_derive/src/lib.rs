@@ -0,0 +1,177 @@
+//! Derive macros for `asr_emu_help`. They are re-exported by that crate when its `derive` feature is enabled,
+//! and should be used from there.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, ToTokens};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Expr, Ident, Index, Result, Type};
+
+/// Consoles a struct can be bound to with `#[emu(...)]`, matching the modules of `asr_emu_help`.
+const CONSOLES: &[&str] = &["ps1", "genesis", "wii"];
+
+/// Generates `FromEndian` for a `#[repr(C)]` struct, by converting each of its fields.
+///
+/// Every field has to implement `FromEndian` and `Copy` itself, or be an array of such values.
+/// Packed structs, with `#[repr(C, packed)]`, are supported as well.
+///
+/// The struct can also be bound to a fixed guest address with `#[emu(<console> = <address>)]`,
+/// where `<console>` is one of `ps1`, `genesis` or `wii`. This adds an `ADDRESS` constant,
+/// along with `read()` and `read_from(&emulator)` functions reading the whole struct from there.
+///
+/// ```ignore
+/// #[derive(Copy, Clone, Pod, Zeroable, FromEndian)]
+/// #[repr(C)]
+/// #[emu(wii = 0x80345678)]
+/// struct Player {
+///     x: f32,
+///     y: f32,
+///     lives: u32,
+/// }
+///
+/// let player = Player::read()?;
+/// ```
+#[proc_macro_derive(FromEndian, attributes(emu))]
+pub fn derive_from_endian(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(Span::call_site(), "`FromEndian` can only be derived for structs"));
+    };
+
+    check_repr(&input)?;
+    let binding = parse_binding(&input)?;
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field in &data.fields {
+        let ty = element_type(&field.ty);
+        where_clause.predicates.push(parse_quote!(#ty: ::asr_emu_help::FromEndian + ::core::marker::Copy));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fields = data.fields.iter().enumerate().map(|(i, field)| {
+        let member = match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => Index::from(i).into_token_stream(),
+        };
+        // Fields are copied out first, as packed structs can't hand out references to them
+        let value = convert(&field.ty, quote!(value));
+        quote!(#member: {
+            let value = self.#member;
+            #value
+        })
+    });
+
+    let mut output = quote! {
+        impl #impl_generics ::asr_emu_help::FromEndian for #name #ty_generics #where_clause {
+            fn from_endian(&self, endian: ::asr_emu_help::Endian) -> Self {
+                Self { #(#fields,)* }
+            }
+        }
+    };
+
+    if let Some((console, address)) = binding {
+        let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+        let read_doc = format!("Reads this struct from its address in the memory of the running {console} emulator.");
+
+        output.extend(quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Guest address this struct is stored at.
+                pub const ADDRESS: u32 = #address;
+
+                #[doc = #read_doc]
+                pub fn read() -> ::core::result::Result<Self, ::asr_emu_help::Error> {
+                    ::asr_emu_help::#console::read(Self::ADDRESS)
+                }
+
+                /// Reads this struct from its address in the memory of the provided emulator.
+                pub fn read_from<P: ::asr_emu_help::shared::MemorySource>(
+                    emulator: &::asr_emu_help::#console::Emulator<P>,
+                ) -> ::core::result::Result<Self, ::asr_emu_help::Error> {
+                    emulator.read(Self::ADDRESS)
+                }
+            }
+        });
+    }
+
+    Ok(output)
+}
+
+/// Returns the expression converting `value`, of type `ty`, to the requested endianness.
+fn convert(ty: &Type, value: TokenStream2) -> TokenStream2 {
+    match ty {
+        Type::Array(array) => {
+            let element = convert(&array.elem, quote!(value[i]));
+            quote!({
+                let value = &#value;
+                ::core::array::from_fn(|i| #element)
+            })
+        }
+        Type::Group(group) => convert(&group.elem, value),
+        Type::Paren(paren) => convert(&paren.elem, value),
+        _ => quote!(::asr_emu_help::FromEndian::from_endian(&#value, endian)),
+    }
+}
+
+/// Returns the type actually converted for a field, looking through arrays.
+fn element_type(ty: &Type) -> &Type {
+    match ty {
+        Type::Array(array) => element_type(&array.elem),
+        Type::Group(group) => element_type(&group.elem),
+        Type::Paren(paren) => element_type(&paren.elem),
+        _ => ty,
+    }
+}
+
+/// Makes sure the layout of the struct is fixed, as it has to match the one in the emulated memory.
+fn check_repr(input: &DeriveInput) -> Result<()> {
+    let mut fixed = false;
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") || meta.path.is_ident("transparent") {
+                fixed = true;
+            } else if meta.input.peek(syn::token::Paren) {
+                // `align(N)` and `packed(N)`
+                let content;
+                syn::parenthesized!(content in meta.input);
+                content.parse::<TokenStream2>()?;
+            }
+            Ok(())
+        })?;
+    }
+
+    if fixed {
+        Ok(())
+    } else {
+        Err(Error::new(input.ident.span(), "`FromEndian` can only be derived for `#[repr(C)]` structs"))
+    }
+}
+
+/// Parses the optional `#[emu(<console> = <address>)]` attribute.
+fn parse_binding(input: &DeriveInput) -> Result<Option<(Ident, Expr)>> {
+    let mut binding = None;
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("emu")) {
+        attr.parse_nested_meta(|meta| {
+            let console = meta.path.get_ident()
+                .filter(|ident| CONSOLES.iter().any(|console| ident == console))
+                .cloned()
+                .ok_or_else(|| meta.error(format_args!("expected one of: {}", CONSOLES.join(", "))))?;
+
+            if binding.is_some() {
+                return Err(meta.error("a struct can only be bound to a single address"));
+            }
+
+            let address: Expr = meta.value()?.parse()?;
+            binding = Some((console, address));
+            Ok(())
+        })?;
+    }
+
+    Ok(binding)
+}
@@ -0,0 +1,82 @@
+use core::fmt;
+
+/// Error returned by every read performed on the emulated memory.
+///
+/// Each variant describes a different failure, so auto splitters can decide
+/// whether to simply retry on the next tick, reset their own state or log the problem.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// No supported emulator is currently attached, or the attached process has been closed.
+    NotAttached,
+    /// The emulator is attached, but the address of the emulated RAM has not been found yet.
+    RamNotFound,
+    /// The requested address, together with the size of the value being read,
+    /// falls outside the memory map of the emulated system.
+    OutOfRange { address: u32, size: usize },
+    /// The address is valid, but reading from the emulator's memory failed.
+    ReadFailed { address: u32, size: usize },
+    /// A null pointer was about to be followed.
+    /// `address` is the location the pointer was read from, or 0 when it's not known.
+    NullPointer { address: u32 },
+    /// The address belongs to a region of the emulated system that the emulator's backend
+    /// can't locate, such as the PS1 scratchpad on some emulators.
+    Unavailable { address: u32, size: usize },
+}
+
+impl Error {
+    /// Returns the address that caused the error, if any.
+    pub const fn address(&self) -> Option<u32> {
+        match self {
+            Self::OutOfRange { address, .. } | Self::ReadFailed { address, .. } | Self::Unavailable { address, .. }
+            | Self::NullPointer { address } => Some(*address),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the error is expected to go away on a later tick, without the auto splitter doing anything:
+    /// while no emulator is running or its RAM hasn't been found, and while a game leaves null pointers during loads.
+    pub const fn is_transient(&self) -> bool {
+        matches!(self, Self::NotAttached | Self::RamNotFound | Self::NullPointer { .. })
+    }
+
+    /// Returns the size, in bytes, of the read that caused the error, if any.
+    pub const fn size(&self) -> Option<usize> {
+        match self {
+            Self::OutOfRange { size, .. } | Self::ReadFailed { size, .. } | Self::Unavailable { size, .. } => Some(*size),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAttached => f.write_str("not attached to any supported emulator"),
+            Self::RamNotFound => f.write_str("the emulated RAM has not been found yet"),
+            Self::OutOfRange { address, size } => write!(f, "address {address:#X} (size {size}) is out of range"),
+            Self::ReadFailed { address, size } => write!(f, "failed to read {size} bytes at address {address:#X}"),
+            Self::NullPointer { address } => write!(f, "null pointer read at address {address:#X}"),
+            Self::Unavailable { address, size } => write!(f, "address {address:#X} (size {size}) can't be located in this emulator"),
+        }
+    }
+}
+
+/// Helpers for the results of the reads.
+///
+/// `asr::Error` carries no information and can't be constructed outside of asr, so these results
+/// aren't converted into asr's error type. Instead, the errors expected to go away on their own
+/// can be turned into a missing value, leaving `?` to propagate the others.
+pub trait ReadResult<T> {
+    /// Turns the errors for which [`Error::is_transient`] is true into `Ok(None)`.
+    fn transient_as_none(self) -> Result<Option<T>, Error>;
+}
+
+impl<T> ReadResult<T> for Result<T, Error> {
+    fn transient_as_none(self) -> Result<Option<T>, Error> {
+        match self {
+            Ok(value) => Ok(Some(value)),
+            Err(error) if error.is_transient() => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+}
@@ -1,94 +1,222 @@
-use core::fmt::Error;
 use asr::{
-    Address, Process,
+    Address,
+    string::ArrayCString,
     sync::Mutex,
+    watcher::Pair,
     primitives::dynamic_endian::{FromEndian, Endian},
 };
-use bytemuck::CheckedBitPattern;
+use bytemuck::{CheckedBitPattern, Pod};
+use crate::{Error, shared::{Console, EmulatorBackend, MemorySource, MemoryDomain, NamePattern, Global, RescanPolicy, update_global, status_global, set_rescan_policy_global, set_selection_global, set_attach_policy_global, read_global, enable_snapshot_global, disable_snapshot_global, refresh_snapshot_global, invalidate_snapshot_global, query_global, memory_domains_global, watches_global, store, configure_global, AttachStatus, AttachPolicy, Candidate, Selection}};
 mod segaclassics;
 mod fusion;
 mod gens;
 mod blastem;
 mod retroarch;
 
-static STATE: Mutex<State> = Mutex::new(State {
-    proc: None,
-});
+static STATE: Mutex<Global<Genesis>> = Mutex::new(Global::new());
+static RETROARCH_CORES: Mutex<Selection<RetroarchCore>> = Mutex::new(Selection::any());
 
-struct State {
-    proc: Option<ProcessInfo>,
-}
+/// The SEGA Genesis / Mega Drive.
+pub struct Genesis;
 
-pub struct ProcessInfo {
-    emulator_type: Emulator,
-    emulator_process: Process,
-    wram_base: Option<Address>,
+/// Address and endianness of the emulated RAM, as found by the backend.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Ram {
+    base: Address,
     endianness: Endian,
 }
 
-impl ProcessInfo {
-    fn attach_process() -> Option<Self> {
-        let (emulator_type, Some(emulator_process)) = PROCESS_NAMES.iter()
-            .map(|name| (name.1, Process::attach(name.0)))
-            .find(|p| p.1.is_some())? else { return None };
+impl Console for Genesis {
+    const NAME: &'static str = "Genesis";
 
-        Some(Self {
-            emulator_type,
-            emulator_process,
-            wram_base: None,
-            endianness: Endian::Little,  // Endianness is supposed to be Little, until stated otherwise in the code
-        })
+    type EmulatorType = EmulatorType;
+    type Backend = Backend;
+    type Ram = Ram;
+
+    const PROCESS_NAMES: &'static [(NamePattern, EmulatorType)] = &PROCESS_NAMES;
+
+    fn backend(emulator_type: EmulatorType) -> Backend {
+        match emulator_type {
+            EmulatorType::Retroarch => Backend::Retroarch(Default::default()),
+            EmulatorType::SegaClassics => Backend::SegaClassics(Default::default()),
+            EmulatorType::Fusion => Backend::Fusion(Default::default()),
+            EmulatorType::Gens => Backend::Gens(Default::default()),
+            EmulatorType::BlastEm => Backend::BlastEm(Default::default()),
+        }
     }
 
-    fn look_for_wram(&mut self) -> Option<Address> {
-        match self.emulator_type {
-            Emulator::Retroarch => retroarch::retroarch(self),
-            Emulator::SegaClassics => segaclassics::segaclassics(self),
-            Emulator::Fusion => fusion::fusion(self),
-            Emulator::Gens => gens::gens(self),
-            Emulator::BlastEm => blastem::blastem(self),
-        }      
+    fn translate(wram: &Ram, offset: u32, size: usize) -> Result<Address, Error> {
+        if (offset > 0xFFFF && offset < 0xFF0000) || offset > 0xFFFFFF || (offset & 0xFFFF) as usize + size > 0x10000 {
+            return Err(Error::OutOfRange { address: offset, size })
+        }
+
+        Ok(Address(wram.base.0 + (offset & 0xFFFF) as u64))
     }
 
-    fn keep_alive(&mut self) -> bool {
-        match self.emulator_type {
-            Emulator::Retroarch => retroarch::keep_alive(self),
-            Emulator::SegaClassics => segaclassics::keep_alive(self),
-            Emulator::Fusion => fusion::keep_alive(self),
-            Emulator::Gens => gens::keep_alive(),
-            Emulator::BlastEm => blastem::keep_alive(),
-        }      
+    fn memory_domain(wram: &Ram, index: usize) -> Option<MemoryDomain> {
+        match index {
+            // Little endian emulators store the RAM as 16-bit words, rather than as bytes in reverse order
+            0 => Some(MemoryDomain { name: "RAM", guest_address: 0xFF0000, size: 0x10000, host_address: wram.base, endian: wram.endianness }),
+            _ => None,
+        }
     }
 }
 
-impl State {
-    fn init(&mut self) -> bool {
-        if self.proc.is_none() {
-            self.proc = ProcessInfo::attach_process()
+/// A handle to a running Genesis / Mega Drive emulator.
+pub type Emulator<P = asr::Process> = crate::shared::Emulator<Genesis, P>;
+
+impl<P: MemorySource> Emulator<P> {
+    /// Restricts which Retroarch cores can be hooked to, and sets the order they are tried in.
+    ///
+    /// This has no effect on other emulators.
+    pub fn set_retroarch_cores(&mut self, cores: Selection<RetroarchCore>) {
+        self.configure_backend(|backend| match backend {
+            Backend::Retroarch(retroarch) => {
+                retroarch.cores = cores;
+                true
+            },
+            _ => false,
+        })
+    }
+
+    /// Returns the Retroarch core the emulated RAM is looked for in, if a supported one is loaded.
+    ///
+    /// This is always `None` for other emulators.
+    pub fn retroarch_core(&self) -> Option<RetroarchCore> {
+        match self.backend() {
+            Backend::Retroarch(retroarch) => retroarch.core,
+            _ => None,
+        }
+    }
+
+    /// Reads raw data from the emulated RAM ignoring all endianness settings.
+    ///
+    /// See [`read_ignoring_endianness`] for details.
+    pub fn read_ignoring_endianness<T: CheckedBitPattern>(&self, offset: u32) -> Result<T, Error> {
+        if offset > 0xFFFF {
+            return Err(Error::OutOfRange { address: offset, size: core::mem::size_of::<T>() })
+        }
+
+        self.read_raw(offset)
+    }
+
+    /// Reads any value from the emulated RAM.
+    ///
+    /// See [`read`] for details about the accepted offsets.
+    pub fn read<T: Pod + FromEndian>(&self, offset: u32) -> Result<T, Error> {
+        let Some(wram) = self.ram() else { return Err(Error::RamNotFound) };
+        let size = core::mem::size_of::<T>();
+
+        let value = match wram.endianness {
+            Endian::Big => self.read_raw::<T>(offset),
+            Endian::Little => {
+                let mut value = T::zeroed();
+                self.read_word_swapped(offset, bytemuck::bytes_of_mut(&mut value)).map(|_| value)
+            },
+        }.map_err(|err| remap_error(err, offset, size))?;
+
+        Ok(value.from_endian(Endian::Big))
+    }
+
+    /// Fills a slice with values read from the emulated RAM.
+    ///
+    /// See [`read_into_slice`] for details about the accepted offsets.
+    pub fn read_into_slice<T: Pod + FromEndian>(&self, offset: u32, slice: &mut [T]) -> Result<(), Error> {
+        let Some(wram) = self.ram() else { return Err(Error::RamNotFound) };
+        let buf: &mut [u8] = bytemuck::cast_slice_mut(slice);
+        let size = buf.len();
+
+        match wram.endianness {
+            Endian::Big => self.read_raw_into_slice(offset, buf),
+            Endian::Little => self.read_word_swapped(offset, buf),
+        }.map_err(|err| remap_error(err, offset, size))?;
+
+        // The buffer now holds the bytes in the same order as the original system
+        for value in slice.iter_mut() {
+            *value = value.from_endian(Endian::Big)
         }
+        Ok(())
+    }
+
+    /// Reads an array of values from the emulated RAM.
+    ///
+    /// See [`read_into_slice`] for details about the accepted offsets.
+    pub fn read_array<T: Pod + FromEndian, const N: usize>(&self, offset: u32) -> Result<[T; N], Error> {
+        let mut array = [T::zeroed(); N];
+        self.read_into_slice(offset, &mut array)?;
+        Ok(array)
+    }
 
-        let Some(game) = &mut self.proc else {
-            return false
-        };
+    /// Reads a fixed-length string from the emulated RAM.
+    ///
+    /// See [`read_string`] for details.
+    pub fn read_string<const N: usize>(&self, offset: u32) -> Result<ArrayCString<N>, Error> {
+        let mut string = ArrayCString::new();
+        self.read_into_slice(offset, bytemuck::bytes_of_mut(&mut string))?;
+        Ok(string)
+    }
+
+    /// Registers a value to be read on every [`Emulator::update`], returning a handle to its last two values.
+    ///
+    /// See [`watch`] for details.
+    pub fn watch<T: Pod + FromEndian>(&mut self, offset: u32) -> Option<Watch<T>> {
+        self.watches_mut().add(offset, |emulator, offset, buf| store(buf, emulator.read::<T>(offset)))
+    }
+
+    /// Reads bytes from an emulator storing the RAM as little endian 16-bit words,
+    /// restoring the byte order of the original system.
+    fn read_word_swapped(&self, mut offset: u32, mut buf: &mut [u8]) -> Result<(), Error> {
+        // Check the whole range at once, so that nothing is read if it doesn't fit
+        let Some(wram) = self.ram() else { return Err(Error::RamNotFound) };
+        Genesis::translate(wram, offset, buf.len())?;
 
-        if !game.emulator_process.is_open() {
-            self.proc = None;
-            return false
+        if offset & 1 != 0 {
+            let Some((first, rest)) = core::mem::take(&mut buf).split_first_mut() else { return Ok(()) };
+            *first = self.read_raw(offset ^ 1)?;
+            offset += 1;
+            buf = rest;
         }
 
-        if game.wram_base.is_none() {
-            game.wram_base = game.look_for_wram();
-            
-            if game.wram_base.is_none() {
-                return false
-            }
+        let even_len = buf.len() & !1;
+        let (words, tail) = buf.split_at_mut(even_len);
+        self.read_raw_into_slice(offset, words)?;
+        for word in words.chunks_exact_mut(2) {
+            word.swap(0, 1)
         }
 
-        if !game.keep_alive() {
-            game.wram_base = None
+        if let [last] = tail {
+            *last = self.read_raw((offset + even_len as u32) ^ 1)?;
         }
+        Ok(())
+    }
+}
+
+/// Reports errors at the offset and size requested by the caller, rather than the ones of the underlying reads.
+fn remap_error(err: Error, address: u32, size: usize) -> Error {
+    match err {
+        Error::OutOfRange { .. } => Error::OutOfRange { address, size },
+        Error::ReadFailed { .. } => Error::ReadFailed { address, size },
+        err => err,
+    }
+}
 
-        game.wram_base.is_some()
+/// A value stored in the Genesis's memory, read again on every update.
+pub type Watch<T> = crate::shared::Watch<Genesis, T>;
+
+impl<T: Pod + FromEndian> Watch<T> {
+    /// Returns the values read by the last two calls to [`update`].
+    ///
+    /// Returns `None` if the last read failed, or if no emulator is attached. Both values are the same
+    /// after the first successful read, as well as after the emulator has been attached again or the RAM has moved.
+    pub fn pair(self) -> Option<Pair<T>> {
+        query_global(&STATE, |emulator| emulator.watches().pair(self)).flatten()
+    }
+
+    /// Returns the values read by the last two updates of the provided emulator.
+    ///
+    /// See [`Watch::pair`] for details.
+    pub fn pair_from<P: MemorySource>(self, emulator: &Emulator<P>) -> Option<Pair<T>> {
+        emulator.watches().pair(self)
     }
 }
 
@@ -102,66 +230,231 @@ impl State {
 /// - Fusion
 /// - Gens
 /// - BlastEm
+///
+/// This is a thin wrapper around a global [`Emulator`] instance.
 pub fn update() -> bool {
-    let state = &mut STATE.lock();
-    state.init()
+    update_global(&STATE, |emulator| emulator.set_retroarch_cores(*RETROARCH_CORES.lock()))
+}
+
+/// Returns the result of the last [`update`], telling apart a missing emulator,
+/// an unsupported core and emulated RAM that has yet to be found.
+pub fn status() -> AttachStatus<EmulatorType> {
+    status_global(&STATE)
+}
+
+/// Sets how often [`update`] looks for the emulated RAM while it can't be found.
+///
+/// By default, [`RescanPolicy::DEFAULT`] is used.
+pub fn set_rescan_policy(policy: RescanPolicy) {
+    set_rescan_policy_global(&STATE, policy)
+}
+
+/// Restricts which emulators [`update`] can attach to, and sets the order they are tried in.
+///
+/// If the emulator currently attached is not allowed anymore, it gets detached.
+pub fn set_emulator_selection(selection: Selection<EmulatorType>) {
+    set_selection_global(&STATE, selection)
+}
+
+/// Sets which process [`update`] attaches to, when several supported emulators are running.
+///
+/// By default, [`AttachPolicy::FirstFound`] is used.
+pub fn set_attach_policy(policy: AttachPolicy) {
+    set_attach_policy_global(&STATE, policy)
+}
+
+/// Lists every supported emulator currently running, along with its process ID.
+///
+/// Any of them can be attached to through [`set_attach_policy`], using [`AttachPolicy::Pid`].
+pub fn candidates() -> impl Iterator<Item = Candidate<EmulatorType>> {
+    Emulator::candidates(&Selection::any())
+}
+
+/// The whole 68k RAM of the Genesis, as a range to be passed to [`enable_snapshot`].
+pub const WHOLE_RAM: (u32, usize) = (0xFF0000, 0x10000);
+
+/// Serves every read from a local copy of the provided guest ranges, taken once per [`update`].
+///
+/// Each range is an address, in any of the formats accepted by [`read`], along with its length in bytes.
+/// Ranges are copied one after the other into `buf`, which can be obtained from a
+/// [`SnapshotBuffer`](crate::shared::SnapshotBuffer). Reads that aren't entirely contained in a single range,
+/// as well as ranges that don't fit in the buffer, keep being performed on the emulator.
+///
+/// This is meant for auto splitters reading many values on each tick, which then need a single read per range.
+/// Passing [`WHOLE_RAM`] copies the whole 64KB of RAM on each update.
+/// The snapshot is kept across emulators being attached and detached.
+pub fn enable_snapshot(buf: &'static mut [u8], ranges: &[(u32, usize)]) {
+    enable_snapshot_global(&STATE, buf, ranges)
+}
+
+/// Goes back to reading every value from the emulator, returning the buffer used by the snapshot.
+pub fn disable_snapshot() -> Option<&'static mut [u8]> {
+    disable_snapshot_global(&STATE)
+}
+
+/// Copies the guest ranges into the snapshot again, outside of [`update`].
+///
+/// Returns false if no snapshot is enabled, or if it could not be refreshed.
+pub fn refresh_snapshot() -> bool {
+    refresh_snapshot_global(&STATE)
+}
+
+/// Discards the content of the snapshot, so values are read from the emulator until the next [`update`] or [`refresh_snapshot`].
+pub fn invalidate_snapshot() {
+    invalidate_snapshot_global(&STATE)
+}
+
+/// Restricts which Retroarch cores [`update`] can hook to, and sets the order they are tried in.
+pub fn set_retroarch_cores(cores: Selection<RetroarchCore>) {
+    *RETROARCH_CORES.lock() = cores;
+    configure_global(&STATE, |emulator| emulator.set_retroarch_cores(cores))
+}
+
+/// Returns the Retroarch core hooked to by [`update`], if a supported one is loaded.
+///
+/// The emulator itself is reported by [`status`].
+pub fn retroarch_core() -> Option<RetroarchCore> {
+    query_global(&STATE, |emulator| emulator.retroarch_core()).flatten()
+}
+
+/// Lists the regions of the emulated memory, along with the addresses they are stored at inside the emulator.
+///
+/// This is meant for advanced uses, such as asr's own pointer paths or signature scans. Nothing is listed
+/// until [`update`] has found the emulated RAM.
+pub fn memory_domains() -> impl Iterator<Item = MemoryDomain> {
+    memory_domains_global(&STATE)
 }
 
 /// Reads  raw data from the emulated RAM ignoring all endianess settings
 /// The same call, performed on two different emulators, can be different
 /// due to the endianness used by the emulator.
 /// 
-/// The offset provided must not be higher than `0xFFFF`, otherwise this method will immediately return `Err()`.
+/// The offset provided must not be higher than `0xFFFF`, otherwise this method will immediately return `Err(Error::OutOfRange)`.
 ///
 /// This call is meant to be used by experienced users.
 pub fn read_ignoring_endianness<T: CheckedBitPattern>(offset: u32) -> Result<T, Error> {
-    if offset > 0xFFFF {
-        return Err(Error)
-    }
-
-    let state = STATE.lock();
-    let Some(proc) = &state.proc else { return Err(Error) };
-    let Some(wram) = &proc.wram_base else { return Err(Error) };
-    
-    if let Ok(output) = proc.emulator_process.read::<T>(Address(wram.0 + offset as u64)) {
-        Ok(output)
-    } else {
-        Err(Error)
-    }
+    read_global(&STATE, |emulator| emulator.read_ignoring_endianness(offset))
 }
 
 /// Reads any value from the emulated RAM.
 /// 
 /// The offset provided is meant to be the same used on the original, big-endian system.
 /// The call will automatically convert the offset and the output value to little endian.
+/// Values of any size, at any offset, are read the same as on the original hardware,
+/// regardless of how the emulator stores the 68k RAM.
 /// 
-/// The offset provided must not be higher than `0xFFFF`, otherwise this method will immediately return `Err()`.
-pub fn read<T: CheckedBitPattern + FromEndian>(offset: u32) -> Result<T, Error> {
-    if (offset > 0xFFFF && offset <= 0xFF0000) || offset > 0xFFFFFF {
-        return Err(Error)
-    }
+/// Offsets can be provided either as `0x0000`-`0xFFFF` or in the 68k mapped form `0xFF0000`-`0xFFFFFF`.
+/// Any other value will make this method immediately return `Err(Error::OutOfRange)`.
+pub fn read<T: Pod + FromEndian>(offset: u32) -> Result<T, Error> {
+    read_global(&STATE, |emulator| emulator.read(offset))
+}
 
-    let state = STATE.lock();
-    let Some(proc) = &state.proc else { return Err(Error) };
-    let Some(wram) = &proc.wram_base else { return Err(Error) };
+/// Fills a slice with values read from the emulated RAM, converting each of them to little endian.
+///
+/// Emulators storing the RAM as little endian 16-bit words get their bytes swapped back
+/// to the original order before any conversion, so values of any size can be read.
+///
+/// Offsets are accepted in the same formats as [`read`]. The whole slice has to fit inside the Genesis' RAM,
+/// otherwise this method will immediately return `Err(Error::OutOfRange)` without reading anything.
+pub fn read_into_slice<T: Pod + FromEndian>(offset: u32, slice: &mut [T]) -> Result<(), Error> {
+    read_global(&STATE, |emulator| emulator.read_into_slice(offset, slice))
+}
 
-    let mut end_offset = offset;
+/// Reads an array of values from the emulated RAM.
+///
+/// See [`read_into_slice`] for details about the accepted offsets.
+pub fn read_array<T: Pod + FromEndian, const N: usize>(offset: u32) -> Result<[T; N], Error> {
+    read_global(&STATE, |emulator| emulator.read_array(offset))
+}
+
+/// Reads a string of up to `N` bytes from the emulated RAM.
+///
+/// The string ends at the first NUL byte, or after `N` bytes for fixed-length fields that fill all of them.
+/// Bytes are always returned in the original order, even on emulators storing the RAM as little endian 16-bit words.
+/// Japanese games usually store their text in Shift-JIS, which can be decoded with `shared::ShiftJis`
+/// when the `shift-jis` feature is enabled.
+///
+/// Offsets are accepted in the same formats as [`read`], and the whole string has to fit inside the Genesis' RAM.
+pub fn read_string<const N: usize>(offset: u32) -> Result<ArrayCString<N>, Error> {
+    read_global(&STATE, |emulator| emulator.read_string(offset))
+}
+
+/// Registers a value to be read on every [`update`], replacing the need for a hand-made [`asr::watcher::Watcher`].
+///
+/// The value is read in the same way as [`read`], and the returned handle gives access to the values read
+/// during the last two updates, along with the helpers of [`Pair`]. Registered values are kept across emulators
+/// being attached and detached, but what has been read so far is discarded, as it is when the RAM moves.
+///
+/// Returns `None` if [`MAX_WATCHES`](crate::shared::MAX_WATCHES) values are already registered,
+/// or if the value is larger than [`MAX_WATCH_SIZE`](crate::shared::MAX_WATCH_SIZE).
+pub fn watch<T: Pod + FromEndian>(offset: u32) -> Option<Watch<T>> {
+    watches_global(&STATE, |watches| watches.add(offset, |emulator: &Emulator, offset, buf| store(buf, emulator.read::<T>(offset))))
+}
+
+/// Stops reading a value registered with [`watch`].
+pub fn unwatch<T: 'static>(watch: Watch<T>) {
+    watches_global(&STATE, |watches| watches.remove(watch))
+}
 
-    // Byte swap the offset if needed
-    if proc.endianness == Endian::Little && core::mem::size_of::<T>() == 1 {
-        if end_offset & 1 == 0 {
-            end_offset += 1
-        } else {
-            end_offset -= 1
+/// Stops reading every value registered with [`watch`].
+pub fn clear_watches() {
+    watches_global(&STATE, |watches| watches.clear())
+}
+
+/// Emulator-specific data, such as pointers cached while looking for the emulated RAM.
+pub enum Backend {
+    Retroarch(retroarch::Retroarch),
+    SegaClassics(segaclassics::SegaClassics),
+    Fusion(fusion::Fusion),
+    Gens(gens::Gens),
+    BlastEm(blastem::BlastEm),
+}
+
+impl EmulatorBackend<Genesis> for Backend {
+    fn find_ram<P: MemorySource>(&mut self, proc: &P) -> Option<Ram> {
+        match self {
+            Self::Retroarch(backend) => backend.find_ram(proc),
+            Self::SegaClassics(backend) => backend.find_ram(proc),
+            Self::Fusion(backend) => backend.find_ram(proc),
+            Self::Gens(backend) => backend.find_ram(proc),
+            Self::BlastEm(backend) => backend.find_ram(proc),
+        }
+    }
+
+    fn keep_alive<P: MemorySource>(&mut self, proc: &P, wram: &mut Ram) -> bool {
+        match self {
+            Self::Retroarch(backend) => backend.keep_alive(proc, wram),
+            Self::SegaClassics(backend) => backend.keep_alive(proc, wram),
+            Self::Fusion(backend) => backend.keep_alive(proc, wram),
+            Self::Gens(backend) => backend.keep_alive(proc, wram),
+            Self::BlastEm(backend) => backend.keep_alive(proc, wram),
+        }
+    }
+
+    fn core_loaded<P: MemorySource>(&self, proc: &P) -> bool {
+        match self {
+            Self::Retroarch(backend) => backend.core_loaded(proc),
+            Self::SegaClassics(backend) => backend.core_loaded(proc),
+            Self::Fusion(backend) => backend.core_loaded(proc),
+            Self::Gens(backend) => backend.core_loaded(proc),
+            Self::BlastEm(backend) => backend.core_loaded(proc),
         }
     }
 
-    let Ok(value) = proc.emulator_process.read::<T>(Address(wram.0 + end_offset as u64)) else { return Err(Error) };
-    Ok(value.from_endian(proc.endianness))
+    fn core_address<P: MemorySource>(&self, proc: &P) -> Option<Address> {
+        match self {
+            Self::Retroarch(backend) => backend.core_address(proc),
+            Self::SegaClassics(backend) => backend.core_address(proc),
+            Self::Fusion(backend) => backend.core_address(proc),
+            Self::Gens(backend) => backend.core_address(proc),
+            Self::BlastEm(backend) => backend.core_address(proc),
+        }
+    }
 }
 
+/// The Genesis emulators supported by this crate.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-enum Emulator {
+pub enum EmulatorType {
     Retroarch,
     SegaClassics,
     Fusion,
@@ -169,11 +462,19 @@ enum Emulator {
     BlastEm,
 }
 
-const PROCESS_NAMES: [(&str, Emulator); 6] = [
-    ("retroarch.exe", Emulator::Retroarch),
-    ("SEGAGameRoom.exe", Emulator::SegaClassics),
-    ("SEGAGenesisClassics.exe", Emulator::SegaClassics),
-    ("Fusion.exe", Emulator::Fusion),
-    ("gens.exe", Emulator::Gens),
-    ("blastem.exe", Emulator::BlastEm),
+/// The Retroarch cores supported by this crate for the Genesis.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RetroarchCore {
+    BlastEm,
+    GenesisPlusGx,
+    GenesisPlusGxWide,
+    PicoDrive,
+}
+
+const PROCESS_NAMES: [(NamePattern, EmulatorType); 5] = [
+    (NamePattern::new("retroarch{.exe,}"), EmulatorType::Retroarch),
+    (NamePattern::new("SEGA{GameRoom,GenesisClassics}.exe"), EmulatorType::SegaClassics),
+    (NamePattern::new("Fusion{.exe,}"), EmulatorType::Fusion),
+    (NamePattern::new("gens{.exe,}"), EmulatorType::Gens),
+    (NamePattern::new("blastem{.exe,}"), EmulatorType::BlastEm),
 ];
\ No newline at end of file
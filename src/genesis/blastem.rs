@@ -1,20 +1,25 @@
 use asr::{Address, signature::Signature, MemoryRangeFlags, primitives::dynamic_endian::Endian};
+use crate::shared::{EmulatorBackend, MemorySource};
+use super::Ram;
 
-pub fn blastem(game: &mut super::ProcessInfo) -> Option<Address> {
-    const SIG: Signature<16> = Signature::new("72 0E 81 E1 FF FF 00 00 66 8B 89 ?? ?? ?? ?? C3");
-    let proc = &game.emulator_process;
-    game.endianness = Endian::Little;
+#[derive(Default)]
+pub struct BlastEm;
 
-    let scanned_address = proc.memory_ranges()
-        .filter(|m| m.flags().unwrap_or_default().contains(MemoryRangeFlags::WRITE) && m.size().unwrap_or_default() == 0x101000)
-        .find_map(|m| SIG.scan_process_range(proc, m.address().unwrap_or_else(|_| Address(0)), m.size().unwrap_or_default()))?
-        .0 + 11;
+impl EmulatorBackend<super::Genesis> for BlastEm {
+    fn find_ram<P: MemorySource>(&mut self, proc: &P) -> Option<Ram> {
+        const SIG: Signature<16> = Signature::new("72 0E 81 E1 FF FF 00 00 66 8B 89 ?? ?? ?? ?? C3");
 
-    let wram = proc.read::<u32>(Address(scanned_address)).ok()?;
+        let scanned_address = proc.memory_ranges()
+            .filter(|m| m.flags.contains(MemoryRangeFlags::WRITE) && m.size == 0x101000)
+            .find_map(|m| proc.scan_signature(&SIG, m.address, m.size))?
+            .0 + 11;
 
-    Some(Address(wram as u64))
-}
+        let wram = proc.read::<u32>(Address(scanned_address))?;
+
+        Some(Ram { base: Address(wram as u64), endianness: Endian::Little })
+    }
 
-pub fn keep_alive() -> bool {
-    true
-}
\ No newline at end of file
+    fn keep_alive<P: MemorySource>(&mut self, _proc: &P, _wram: &mut Ram) -> bool {
+        true
+    }
+}
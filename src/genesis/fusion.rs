@@ -1,44 +1,37 @@
-use asr::{Address, signature::Signature, sync::Mutex, primitives::dynamic_endian::Endian};
+use asr::{Address, signature::Signature, primitives::dynamic_endian::Endian};
+use crate::shared::{EmulatorBackend, MemorySource, main_module};
+use super::Ram;
 
-static STATICDATA: Mutex<StaticData> = Mutex::new(StaticData {
-    addr: Address(0),
-});
-
-struct StaticData {
+#[derive(Default)]
+pub struct Fusion {
     addr: Address,
 }
 
-pub fn fusion(game: &mut super::ProcessInfo) -> Option<Address> {
-    const SIG: Signature<4> = Signature::new("75 2F 6A 01");
-    let proc = &game.emulator_process;
-
-    let (Some(main_module_address), Some(main_module_size)) = super::PROCESS_NAMES.iter()
-        .filter(|p| p.1 == super::Emulator::Fusion)
-        .map(|m| (proc.get_module_address(m.0).ok(), proc.get_module_size(m.0).ok()))
-        .find(|m| m.0.is_some() && m.1.is_some())? else { return None };
+impl EmulatorBackend<super::Genesis> for Fusion {
+    fn find_ram<P: MemorySource>(&mut self, proc: &P) -> Option<Ram> {
+        const SIG: Signature<4> = Signature::new("75 2F 6A 01");
 
-    let ptr = SIG.scan_process_range(proc, main_module_address, main_module_size)?.0 + 1;
+        let (main_module_name, main_module_address) = main_module::<super::Genesis, _>(proc, super::EmulatorType::Fusion)?;
+        let main_module_size = proc.module_size(&main_module_name)?;
 
-    let addr = ptr + proc.read::<u8>(Address(ptr)).ok()? as u64 + 3;
-    let addr = Address(proc.read::<u32>(Address(addr)).ok()? as u64);
+        let ptr = proc.scan_signature(&SIG, main_module_address, main_module_size)?.0 + 1;
 
-    let mut static_data = STATICDATA.lock();
-    static_data.addr = addr;
+        let addr = ptr + proc.read::<u8>(Address(ptr))? as u64 + 3;
+        let addr = Address(proc.read::<u32>(Address(addr))? as u64);
 
-    let addr = proc.read::<u32>(addr).ok()?;
-    
-    game.endianness = Endian::Big;
+        self.addr = addr;
 
-    Some(Address(addr as u64))
-}
+        let addr = proc.read::<u32>(addr)?;
 
-pub fn keep_alive(game: &mut super::ProcessInfo) -> bool {
-    let static_data = STATICDATA.lock();
+        Some(Ram { base: Address(addr as u64), endianness: Endian::Big })
+    }
 
-    if let Ok(addr) = game.emulator_process.read::<u32>(static_data.addr) {
-        game.wram_base = Some(Address(addr as u64));
-        true
-    } else {
-        false
+    fn keep_alive<P: MemorySource>(&mut self, proc: &P, wram: &mut Ram) -> bool {
+        if let Some(addr) = proc.read::<u32>(self.addr) {
+            wram.base = Address(addr as u64);
+            true
+        } else {
+            false
+        }
     }
-}
\ No newline at end of file
+}
@@ -1,27 +1,31 @@
 use asr::{Address, signature::Signature, primitives::dynamic_endian::Endian};
+use crate::shared::{EmulatorBackend, MemorySource, main_module};
+use super::Ram;
 
-pub fn gens(game: &mut super::ProcessInfo) -> Option<Address> {
-    const SIG: Signature<10> = Signature::new("72 ?? 81 ?? FF FF 00 00 66 8B");
-    let proc = &game.emulator_process;
+#[derive(Default)]
+pub struct Gens;
 
-    let (Some(main_module_address), Some(main_module_size)) = super::PROCESS_NAMES.iter()
-        .filter(|p| p.1 == super::Emulator::Gens)
-        .map(|m| (proc.get_module_address(m.0).ok(), proc.get_module_size(m.0).ok()))
-        .find(|m| m.0.is_some() && m.1.is_some())? else { return None };
+impl EmulatorBackend<super::Genesis> for Gens {
+    fn find_ram<P: MemorySource>(&mut self, proc: &P) -> Option<Ram> {
+        const SIG: Signature<10> = Signature::new("72 ?? 81 ?? FF FF 00 00 66 8B");
 
-    let ptr = SIG.scan_process_range(proc, main_module_address, main_module_size)?.0 + 11;
+        let (main_module_name, main_module_address) = main_module::<super::Genesis, _>(proc, super::EmulatorType::Gens)?;
+        let main_module_size = proc.module_size(&main_module_name)?;
 
-    game.endianness = if proc.read::<u8>(Address(ptr + 4)).ok()? == 0x86 {
-        Endian::Big
-    } else {
-        Endian::Little
-    };
+        let ptr = proc.scan_signature(&SIG, main_module_address, main_module_size)?.0 + 11;
 
-    let wram = proc.read::<u32>(Address(ptr)).ok()? as u64;
+        let endianness = if proc.read::<u8>(Address(ptr + 4))? == 0x86 {
+            Endian::Big
+        } else {
+            Endian::Little
+        };
 
-    Some(Address(wram))
-}
+        let wram = proc.read::<u32>(Address(ptr))? as u64;
+
+        Some(Ram { base: Address(wram), endianness })
+    }
 
-pub fn keep_alive() -> bool {
-    true
-}
\ No newline at end of file
+    fn keep_alive<P: MemorySource>(&mut self, _proc: &P, _wram: &mut Ram) -> bool {
+        true
+    }
+}
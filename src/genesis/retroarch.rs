@@ -1,85 +1,86 @@
-use asr::{Address, signature::Signature, MemoryRangeFlags, sync::Mutex, primitives::dynamic_endian::Endian};
+use asr::{Address, signature::Signature, MemoryRangeFlags, primitives::dynamic_endian::Endian};
+use crate::shared::{EmulatorBackend, MemorySource, NamePattern, Selection, main_module};
+use super::RetroarchCore;
+use super::Ram;
 
-static STATICDATA: Mutex<StaticData> = Mutex::new(StaticData {
-    core_base: Address(0),
-});
+const SUPPORTED_CORES: [(NamePattern, RetroarchCore); 4] = [
+    (NamePattern::new("blastem_libretro{.dll,.so,.dylib}"), RetroarchCore::BlastEm),
+    (NamePattern::new("genesis_plus_gx_libretro{.dll,.so,.dylib}"), RetroarchCore::GenesisPlusGx),
+    (NamePattern::new("genesis_plus_gx_wide_libretro{.dll,.so,.dylib}"), RetroarchCore::GenesisPlusGxWide),
+    (NamePattern::new("picodrive_libretro{.dll,.so,.dylib}"), RetroarchCore::PicoDrive),
+];
 
-struct StaticData {
+#[derive(Default)]
+pub struct Retroarch {
     core_base: Address,
+    pub(super) cores: Selection<RetroarchCore>,
+    pub(super) core: Option<RetroarchCore>,
 }
 
-pub fn retroarch(game: &mut super::ProcessInfo) -> Option<Address> {
-    const SUPPORTED_CORES: [&str; 4] = [
-        "blastem_libretro.dll",
-        "genesis_plus_gx_libretro.dll",
-        "genesis_plus_gx_wide_libretro.dll",
-        "picodrive_libretro.dll",
-    ]; 
+impl EmulatorBackend<super::Genesis> for Retroarch {
+    fn find_ram<P: MemorySource>(&mut self, proc: &P) -> Option<Ram> {
+        self.core = None;
+        let (_, main_module_address) = main_module::<super::Genesis, _>(proc, super::EmulatorType::Retroarch)?;
 
-    let proc = &game.emulator_process;
-    let mut static_data = STATICDATA.lock();
+        let is_64_bit = crate::shared::check_for_64_bit(proc, main_module_address);
 
-    let Some(main_module_address) = super::PROCESS_NAMES.iter()
-        .filter(|p| p.1 == super::Emulator::Retroarch)
-        .map(|m| proc.get_module_address(m.0).ok())
-        .find(|m| m.is_some())? else { return None };
+        let (core, (core_name, core_address)) = self.cores.apply(&SUPPORTED_CORES, |c| c.1)
+            .find_map(|&(pattern, core)| Some((core, pattern.find_module(proc)?)))?;
 
-    let is_64_bit = crate::shared::check_for_64_bit(proc, main_module_address);
+        self.core_base = core_address;
+        self.core = Some(core);
 
-    let (&core_name, Ok(core_address)) = SUPPORTED_CORES.iter()
-        .map(|m| (m, proc.get_module_address(m)))
-        .find(|m| m.1.is_ok())? else { return None };
+        if core == RetroarchCore::BlastEm {
+            // BlastEm
+            const SIG: Signature<16> = Signature::new("72 0E 81 E1 FF FF 00 00 66 8B 89 ?? ?? ?? ?? C3");
+            let scanned_address = proc.memory_ranges()
+                .filter(|m| m.flags.contains(MemoryRangeFlags::WRITE) && m.size == 0x101000)
+                .find_map(|m| proc.scan_signature(&SIG, m.address, m.size))?
+                .0 + 11;
 
-    static_data.core_base = core_address;
+            let wram = proc.read::<u32>(Address(scanned_address))?;
 
-    if core_name == SUPPORTED_CORES[0] {
-        game.endianness = Endian::Little;
-
-        // BlastEm
-        const SIG: Signature<16> = Signature::new("72 0E 81 E1 FF FF 00 00 66 8B 89 ?? ?? ?? ?? C3");
-        let scanned_address = proc.memory_ranges()
-            .filter(|m| m.flags().unwrap_or_default().contains(MemoryRangeFlags::WRITE) && m.size().unwrap_or_default() == 0x101000)
-            .find_map(|m| SIG.scan_process_range(proc, m.address().unwrap_or_else(|_| Address(0)), m.size().unwrap_or_default()))?
-            .0 + 11;
-
-        let wram = proc.read::<u32>(Address(scanned_address)).ok()?;
-
-        Some(Address(wram as u64))
-    } else if core_name == SUPPORTED_CORES[1] || core_name == SUPPORTED_CORES[2] {
-        game.endianness = Endian::Little;
-
-        // Genesis plus GX
-        if is_64_bit {
-            const SIG_64: Signature<10> = Signature::new("48 8D 0D ?? ?? ?? ?? 4C 8B 2D");
-            let addr = SIG_64.scan_process_range(proc, core_address, proc.get_module_size(core_name).ok()?)?.0 as i64 + 3;
-            let wram = addr + 0x4 + proc.read::<i32>(Address(addr as u64)).ok()? as i64;
-            Some(Address(wram as u64))
+            Some(Ram { base: Address(wram as u64), endianness: Endian::Little })
+        } else if core == RetroarchCore::GenesisPlusGx || core == RetroarchCore::GenesisPlusGxWide {
+            // Genesis plus GX
+            if is_64_bit {
+                const SIG_64: Signature<10> = Signature::new("48 8D 0D ?? ?? ?? ?? 4C 8B 2D");
+                let addr = proc.scan_signature(&SIG_64, core_address, proc.module_size(&core_name)?)?.0 as i64 + 3;
+                let wram = addr + 0x4 + proc.read::<i32>(Address(addr as u64))? as i64;
+                Some(Ram { base: Address(wram as u64), endianness: Endian::Little })
+            } else {
+                const SIG_32: Signature<7> = Signature::new("A3 ?? ?? ?? ?? 29 F9");
+                let ptr = proc.scan_signature(&SIG_32, core_address, proc.module_size(&core_name)?)?.0 + 1;
+                let wram = proc.read::<u32>(Address(ptr))? as u64;
+                Some(Ram { base: Address(wram), endianness: Endian::Little })
+            }
+        } else if core == RetroarchCore::PicoDrive {
+            // Picodrive
+            if is_64_bit {
+                const SIG_64: Signature<9> = Signature::new("48 8D 0D ?? ?? ?? ?? 41 B8");
+                let addr = proc.scan_signature(&SIG_64, core_address, proc.module_size(&core_name)?)?.0 as i64 + 3;
+                let wram = addr + 0x4 + proc.read::<i32>(Address(addr as u64))? as i64;
+                Some(Ram { base: Address(wram as u64), endianness: Endian::Little })
+            } else {
+                const SIG_32: Signature<8> = Signature::new("B9 ?? ?? ?? ?? C1 EF 10");
+                let ptr = proc.scan_signature(&SIG_32, core_address, proc.module_size(&core_name)?)?.0 + 1;
+                let wram = proc.read::<u32>(Address(ptr))? as u64;
+                Some(Ram { base: Address(wram), endianness: Endian::Little })
+            }        
         } else {
-            const SIG_32: Signature<7> = Signature::new("A3 ?? ?? ?? ?? 29 F9");
-            let ptr = SIG_32.scan_process_range(proc, core_address, proc.get_module_size(core_name).ok()?)?.0 + 1;
-            let wram = proc.read::<u32>(Address(ptr)).ok()? as u64;
-            Some(Address(wram))        }
-    } else if core_name == SUPPORTED_CORES[3] {
-        game.endianness = Endian::Little;
+            None
+        }
+    }
 
-        // Picodrive
-        if is_64_bit {
-            const SIG_64: Signature<9> = Signature::new("48 8D 0D ?? ?? ?? ?? 41 B8");
-            let addr = SIG_64.scan_process_range(proc, core_address, proc.get_module_size(core_name).ok()?)?.0 as i64 + 3;
-            let wram = addr + 0x4 + proc.read::<i32>(Address(addr as u64)).ok()? as i64;
-            Some(Address(wram as u64))
-        } else {
-            const SIG_32: Signature<8> = Signature::new("B9 ?? ?? ?? ?? C1 EF 10");
-            let ptr = SIG_32.scan_process_range(proc, core_address, proc.get_module_size(core_name).ok()?)?.0 + 1;
-            let wram = proc.read::<u32>(Address(ptr)).ok()? as u64;
-            Some(Address(wram))
-        }        
-    } else {
-        None
+    fn keep_alive<P: MemorySource>(&mut self, proc: &P, _wram: &mut Ram) -> bool {
+        proc.read::<u8>(self.core_base).is_some()
     }
-}
 
-pub fn keep_alive(game: &super::ProcessInfo) -> bool {
-    let static_data = STATICDATA.lock();
-    game.emulator_process.read::<u8>(static_data.core_base).is_ok()
+    fn core_loaded<P: MemorySource>(&self, proc: &P) -> bool {
+        self.core_address(proc).is_some()
+    }
+
+    fn core_address<P: MemorySource>(&self, proc: &P) -> Option<Address> {
+        self.cores.apply(&SUPPORTED_CORES, |c| c.1).find_map(|c| c.0.find_module(proc)).map(|m| m.1)
+    }
 }
\ No newline at end of file
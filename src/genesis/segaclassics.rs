@@ -1,52 +1,42 @@
-use asr::{Address, signature::Signature, primitives::dynamic_endian::Endian, sync::Mutex};
+use asr::{Address, signature::Signature, primitives::dynamic_endian::Endian};
+use crate::shared::{EmulatorBackend, MemorySource, NamePattern, main_module};
+use super::Ram;
 
-static STATICDATA: Mutex<StaticData> = Mutex::new(StaticData {
-    addr: Address(0),
-});
-
-struct StaticData {
+#[derive(Default)]
+pub struct SegaClassics {
     addr: Address,
 }
 
-pub fn segaclassics(game: &mut super::ProcessInfo) -> Option<Address> {
-    const SIG_GAMEROOM: Signature<16> = Signature::new("C7 05 ???????? ???????? A3 ???????? A3");
-    const SIG_SEGACLASSICS: Signature<8> = Signature::new("89 2D ???????? 89 0D");
-    const GENESISWRAPPERDLL: &str = "GenesisEmuWrapper.dll";
-
-    let proc = &game.emulator_process;
-
-    let mut ptr: u64 = 0;
+impl EmulatorBackend<super::Genesis> for SegaClassics {
+    fn find_ram<P: MemorySource>(&mut self, proc: &P) -> Option<Ram> {
+        const SIG_GAMEROOM: Signature<16> = Signature::new("C7 05 ???????? ???????? A3 ???????? A3");
+        const SIG_SEGACLASSICS: Signature<8> = Signature::new("89 2D ???????? 89 0D");
+        const GENESISWRAPPERDLL: NamePattern = NamePattern::new("GenesisEmuWrapper.dll");
 
+        let mut ptr = if let Some((name, module)) = GENESISWRAPPERDLL.find_module(proc) {
+            proc.scan_signature(&SIG_GAMEROOM, module, proc.module_size(&name)?)?.0 + 2
+        } else {
+            let (main_module_name, main_module_address) = main_module::<super::Genesis, _>(proc, super::EmulatorType::SegaClassics)?;
+            let main_module_size = proc.module_size(&main_module_name)?;
 
-    if let (Ok(module), Ok(size)) = (proc.get_module_address(GENESISWRAPPERDLL), proc.get_module_size(GENESISWRAPPERDLL)) {
-        ptr = SIG_GAMEROOM.scan_process_range(proc, module, size)?.0 + 2;
-    } else {
-        let (Some(main_module_address), Some(main_module_size)) = super::PROCESS_NAMES.iter()
-            .filter(|p| p.1 == super::Emulator::SegaClassics)
-            .map(|m| (proc.get_module_address(m.0).ok(), proc.get_module_size(m.0).ok()))
-            .find(|m| m.0.is_some() && m.1.is_some())? else { return None };
-
-        ptr = SIG_SEGACLASSICS.scan_process_range(proc, main_module_address, main_module_size)?.0 + 8;
-    }
+            proc.scan_signature(&SIG_SEGACLASSICS, main_module_address, main_module_size)?.0 + 8
+        };
 
-    ptr = proc.read::<u32>(Address(ptr)).ok()? as u64;
+        ptr = proc.read::<u32>(Address(ptr))? as u64;
 
-    let mut static_data = STATICDATA.lock();
-    static_data.addr = Address(ptr);
-    game.endianness = Endian::Little;
+        self.addr = Address(ptr);
 
-    ptr = proc.read::<u32>(static_data.addr).ok()? as u64;
+        ptr = proc.read::<u32>(self.addr)? as u64;
 
-    Some(Address(ptr))
-}
-
-pub fn keep_alive(game: &mut super::ProcessInfo) -> bool {
-    let static_data = STATICDATA.lock();
+        Some(Ram { base: Address(ptr), endianness: Endian::Little })
+    }
 
-    if let Ok(addr) = game.emulator_process.read::<u32>(static_data.addr) {
-        game.wram_base = Some(Address(addr as u64));
-        true
-    } else {
-        false
+    fn keep_alive<P: MemorySource>(&mut self, proc: &P, wram: &mut Ram) -> bool {
+        if let Some(addr) = proc.read::<u32>(self.addr) {
+            wram.base = Address(addr as u64);
+            true
+        } else {
+            false
+        }
     }
-}
\ No newline at end of file
+}
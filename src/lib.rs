@@ -1,6 +1,15 @@
 #![no_std]
 pub mod shared;
 
+mod error;
+pub use error::{Error, ReadResult};
+
+pub use asr::primitives::dynamic_endian::{Endian, FromEndian};
+
+/// Derives `FromEndian` for `#[repr(C)]` structs, optionally binding them to a guest address.
+#[cfg(feature = "derive")]
+pub use asr_emu_help_derive::FromEndian;
+
 #[cfg(feature = "ps1")]
 pub mod ps1;
 
@@ -8,4 +17,4 @@ pub mod ps1;
 pub mod genesis;
 
 #[cfg(feature = "wii")]
-pub mod wii;
\ No newline at end of file
+pub mod wii;
@@ -1,101 +1,327 @@
-use core::fmt::Error;
-use asr::{Address, Process, sync::Mutex};
-use bytemuck::CheckedBitPattern;
+use asr::{Address, string::ArrayCString, sync::Mutex, watcher::Pair, primitives::dynamic_endian::Endian};
+use bytemuck::{CheckedBitPattern, Pod};
+use crate::{Error, shared::{Console, EmulatorBackend, MemorySource, MemoryDomain, EmuPtr, NamePattern, Global, RescanPolicy, update_global, status_global, set_rescan_policy_global, set_selection_global, set_attach_policy_global, read_global, enable_snapshot_global, disable_snapshot_global, refresh_snapshot_global, invalidate_snapshot_global, query_global, memory_domains_global, watches_global, store, configure_global, AttachStatus, AttachPolicy, Candidate, Selection}};
 mod epsxe;
 mod xebra;
 mod pcsx_redux;
 mod duckstation;
 mod psxfin;
 mod retroarch;
+mod game_id;
+mod kernel;
+mod regions;
 
-static STATE: Mutex<State> = Mutex::new(State {
-    proc: None,
-});
+pub use game_id::{GameId, Region};
+pub use kernel::{Event, Thread};
 
-struct State {
-    proc: Option<ProcessInfo>,
-}
+static STATE: Mutex<Global<Ps1>> = Mutex::new(Global::new());
+static RETROARCH_CORES: Mutex<Selection<RetroarchCore>> = Mutex::new(Selection::any());
+
+/// The Sony PlayStation.
+pub struct Ps1;
 
-pub struct ProcessInfo {
-    emulator_type: Emulator,
-    emulator_process: Process,
-    wram_base: Option<Address>,
+/// Addresses of the emulated RAM, scratchpad and BIOS, along with the size of the RAM, as found by the backend.
+///
+/// Where the scratchpad and the BIOS are found:
+/// - PCSX-Redux (64-bit) exposes both of them.
+/// - PCSX ReARMed, DuckStation, SwanStation and Beetle PSX have the pointers declared next to the one to the RAM
+///   checked until they are found. Only PCSX ReARMed keeps the scratchpad there, at the start of its buffer of the
+///   hardware registers, which is only recognized once the BIOS has set them up.
+/// - ePSXe, pSX, XEBRA and 32-bit PCSX-Redux have their memory near the RAM scanned once, when they are hooked,
+///   so the scratchpad is only found if the BIOS has started by then.
+///
+/// The BIOS is recognized by the reset vector of Sony's BIOSes, so replacement BIOSes, such as HLE ones, can't be found.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Ram {
+    base: Address,
+    size: u32,
+    scratchpad: Option<Address>,
+    bios: Option<Address>,
 }
 
-impl ProcessInfo {
-    fn attach_process() -> Option<Self> {
-        let (emulator_type, Some(emulator_process)) = PROCESS_NAMES
-            .iter()
-            .map(|name| (name.1, Process::attach(name.0)))
-            .find(|p| p.1.is_some())? else { return None };
+impl Ram {
+    const fn new(base: Address) -> Self {
+        Self { base, size: RAM_SIZE, scratchpad: None, bios: None }
+    }
 
-        Some(Self {
-            emulator_type,
-            emulator_process,
-            wram_base: None,
-        })
+    /// Returns the address of the main RAM.
+    pub const fn base(&self) -> Address {
+        self.base
     }
 
-    fn look_for_wram(&self) -> Option<Address> {
-        let addr = match self.emulator_type {
-            Emulator::Epsxe => epsxe::epsxe(self),
-            Emulator::PsxFin => psxfin::psxfin(self),
-            Emulator::Duckstation => duckstation::duckstation(self),
-            Emulator::Retroarch => retroarch::retroarch(self),
-            Emulator::PcsxRedux => pcsx_redux::pcsx_redux(self),
-            Emulator::Xebra => xebra::xebra(self),
-        };
+    /// Returns the size of the main RAM: 2MB on retail consoles, or 8MB when the emulator is set to emulate a dev kit.
+    pub const fn size(&self) -> u32 {
+        self.size
+    }
 
-        addr
+    /// Returns the address of the 1KB scratchpad, if the backend knows where the emulator stores it.
+    pub const fn scratchpad(&self) -> Option<Address> {
+        self.scratchpad
     }
 
-    fn keep_alive(&mut self) -> bool {
-        match self.emulator_type {
-            Emulator::Epsxe => epsxe::keep_alive(),
-            Emulator::PsxFin => psxfin::keep_alive(),
-            Emulator::Duckstation => duckstation::keep_alive(self),
-            Emulator::Retroarch => retroarch::keep_alive(self),
-            Emulator::PcsxRedux => pcsx_redux::keep_alive(self),
-            Emulator::Xebra => xebra::keep_alive(),
-        }
+    /// Returns the address of the 512KB BIOS, if the backend knows where the emulator stores it.
+    pub const fn bios(&self) -> Option<Address> {
+        self.bios
     }
 }
 
-impl State {
-    fn init(&mut self) -> bool {
-        if self.proc.is_none() {
-            self.proc = ProcessInfo::attach_process()
+impl Console for Ps1 {
+    const NAME: &'static str = "PS1";
+
+    type EmulatorType = EmulatorType;
+    type Backend = Backend;
+    type Ram = Ram;
+
+    const PROCESS_NAMES: &'static [(NamePattern, EmulatorType)] = &PROCESS_NAMES;
+
+    fn backend(emulator_type: EmulatorType) -> Backend {
+        match emulator_type {
+            EmulatorType::Epsxe => Backend::Epsxe(Default::default()),
+            EmulatorType::PsxFin => Backend::PsxFin(Default::default()),
+            EmulatorType::Duckstation => Backend::Duckstation(Default::default()),
+            EmulatorType::Retroarch => Backend::Retroarch(Default::default()),
+            EmulatorType::PcsxRedux => Backend::PcsxRedux(Default::default()),
+            EmulatorType::Xebra => Backend::Xebra(Default::default()),
+        }
+    }
+
+    fn translate(ram: &Ram, offset: u32, size: usize) -> Result<Address, Error> {
+        // The RAM and the BIOS are mirrored in KUSEG, KSEG0 and KSEG1, which only differ in the top 3 bits
+        let segment = offset & 0xE0000000;
+        let physical = offset & 0x1FFFFFFF;
+        let end = physical as u64 + size.saturating_sub(1) as u64;
+
+        if !matches!(segment, KUSEG | KSEG0 | KSEG1) {
+            return Err(Error::OutOfRange { address: offset, size })
+        }
+
+        // The scratchpad is inside the CPU, so it can't be accessed through the uncached KSEG1
+        if (SCRATCHPAD..SCRATCHPAD + SCRATCHPAD_SIZE).contains(&physical) {
+            if segment == KSEG1 || end >= (SCRATCHPAD + SCRATCHPAD_SIZE) as u64 {
+                return Err(Error::OutOfRange { address: offset, size })
+            }
+
+            let Some(scratchpad) = ram.scratchpad else { return Err(Error::Unavailable { address: offset, size }) };
+            return Ok(Address(scratchpad.0 + (physical - SCRATCHPAD) as u64))
+        }
+
+        if physical >= BIOS & 0x1FFFFFFF {
+            if end >= ((BIOS & 0x1FFFFFFF) + BIOS_SIZE) as u64 {
+                return Err(Error::OutOfRange { address: offset, size })
+            }
+
+            let Some(bios) = ram.bios else { return Err(Error::Unavailable { address: offset, size }) };
+            return Ok(Address(bios.0 + (physical - (BIOS & 0x1FFFFFFF)) as u64))
         }
 
-        let Some(game) = &mut self.proc else {
-            return false
+        if end >= ram.size as u64 {
+            return Err(Error::OutOfRange { address: offset, size })
         };
 
-        if !game.emulator_process.is_open() {
-            self.proc = None;
-            return false
+        Ok(Address(ram.base.0 + physical as u64))
+    }
+
+    fn memory_domain(ram: &Ram, index: usize) -> Option<MemoryDomain> {
+        let main = MemoryDomain { name: "RAM", guest_address: KSEG0, size: ram.size, host_address: ram.base, endian: Endian::Little };
+        let scratchpad = ram.scratchpad.map(|host_address| {
+            MemoryDomain { name: "Scratchpad", guest_address: SCRATCHPAD, size: SCRATCHPAD_SIZE, host_address, endian: Endian::Little }
+        });
+
+        let bios = ram.bios.map(|host_address| {
+            MemoryDomain { name: "BIOS", guest_address: BIOS, size: BIOS_SIZE, host_address, endian: Endian::Little }
+        });
+
+        [Some(main), scratchpad, bios].into_iter().flatten().nth(index)
+    }
+}
+
+/// A handle to a running PS1 emulator.
+pub type Emulator<P = asr::Process> = crate::shared::Emulator<Ps1, P>;
+
+impl<P: MemorySource> Emulator<P> {
+    /// Restricts which Retroarch cores can be hooked to, and sets the order they are tried in.
+    ///
+    /// This has no effect on other emulators.
+    pub fn set_retroarch_cores(&mut self, cores: Selection<RetroarchCore>) {
+        self.configure_backend(|backend| match backend {
+            Backend::Retroarch(retroarch) => {
+                retroarch.cores = cores;
+                true
+            },
+            _ => false,
+        })
+    }
+
+    /// Returns the Retroarch core the emulated RAM is looked for in, if a supported one is loaded.
+    ///
+    /// This is always `None` for other emulators.
+    pub fn retroarch_core(&self) -> Option<RetroarchCore> {
+        match self.backend() {
+            Backend::Retroarch(retroarch) => retroarch.core,
+            _ => None,
         }
+    }
 
-        if game.wram_base.is_none() {
-            game.wram_base = game.look_for_wram();
-            
-            if game.wram_base.is_none() {
-                return false
+    /// Returns the serial number and region of the running game.
+    ///
+    /// See [`game_id`] for details.
+    pub fn game_id(&self) -> Option<GameId> {
+        let mut buf = [0; 0x400];
+        let mut offset = 0;
+
+        // Consecutive chunks overlap, so paths crossing them are still found
+        loop {
+            let len = buf.len().min((game_id::KERNEL_SIZE - offset) as usize);
+            self.read_into_slice(offset, &mut buf[..len]).ok()?;
+
+            if let Some(id) = GameId::find(&buf[..len]) {
+                return Some(id)
             }
+
+            if offset + len as u32 == game_id::KERNEL_SIZE {
+                return None
+            }
+
+            offset += (len - game_id::MAX_PATH_LEN) as u32;
+        }
+    }
+
+    /// Reads any value from the emulated RAM.
+    ///
+    /// See [`read`] for details about the accepted offsets.
+    pub fn read<T: CheckedBitPattern>(&self, offset: u32) -> Result<T, Error> {
+        self.read_raw(offset)
+    }
+
+    /// Follows a path of pointers stored in the emulated RAM, returning the address it leads to.
+    ///
+    /// See [`read_pointer_path`] for details.
+    pub fn resolve_pointer_path(&self, base: u32, offsets: &[u32]) -> Result<u32, Error> {
+        self.resolve_pointer_path_with(base, offsets, |emulator, address| emulator.read::<u32>(address))
+    }
+
+    /// Follows a path of pointers stored in the emulated RAM, then reads the value it leads to.
+    ///
+    /// See [`read_pointer_path`] for details.
+    pub fn read_pointer_path<T: CheckedBitPattern>(&self, base: u32, offsets: &[u32]) -> Result<T, Error> {
+        self.read(self.resolve_pointer_path(base, offsets)?)
+    }
+
+    /// Reads any value from the BIOS.
+    ///
+    /// See [`read_bios`] for details.
+    pub fn read_bios<T: CheckedBitPattern>(&self, offset: u32) -> Result<T, Error> {
+        if offset as u64 + core::mem::size_of::<T>() as u64 > BIOS_SIZE as u64 {
+            return Err(Error::OutOfRange { address: offset, size: core::mem::size_of::<T>() })
+        }
+
+        self.read(BIOS + offset)
+    }
+
+    /// Reads an event control block from the kernel's event table.
+    ///
+    /// See [`event`] for details.
+    pub fn event(&self, index: u32) -> Result<Event, Error> {
+        let [table, size] = self.read::<[u32; 2]>(kernel::TABLE_OF_TABLES + kernel::EVENTS)?;
+        let address = table.wrapping_add(index.wrapping_mul(core::mem::size_of::<Event>() as u32));
+
+        if table == 0 {
+            return Err(Error::NullPointer { address: kernel::TABLE_OF_TABLES + kernel::EVENTS })
+        } else if index >= size / core::mem::size_of::<Event>() as u32 {
+            return Err(Error::OutOfRange { address, size: core::mem::size_of::<Event>() })
         }
 
-        if !game.keep_alive() {
-            game.wram_base = None
+        self.read(address)
+    }
+
+    /// Reads the control block of the current thread.
+    ///
+    /// See [`current_thread`] for details.
+    pub fn current_thread(&self) -> Result<Thread, Error> {
+        // The process control block starts with a pointer to the current thread
+        self.read_pointer_path(kernel::TABLE_OF_TABLES + kernel::PROCESSES, &[0, 0, 0])
+    }
+
+    /// Fills a slice with values read from the emulated RAM.
+    ///
+    /// See [`read_into_slice`] for details about the accepted offsets.
+    pub fn read_into_slice<T: Pod>(&self, offset: u32, slice: &mut [T]) -> Result<(), Error> {
+        self.read_raw_into_slice(offset, slice)
+    }
+
+    /// Reads an array of values from the emulated RAM.
+    ///
+    /// See [`read_into_slice`] for details about the accepted offsets.
+    pub fn read_array<T: Pod, const N: usize>(&self, offset: u32) -> Result<[T; N], Error> {
+        let mut array = [T::zeroed(); N];
+        self.read_into_slice(offset, &mut array)?;
+        Ok(array)
+    }
+
+    /// Reads a fixed-length string from the emulated RAM.
+    ///
+    /// See [`read_string`] for details.
+    pub fn read_string<const N: usize>(&self, offset: u32) -> Result<ArrayCString<N>, Error> {
+        let mut string = ArrayCString::new();
+        self.read_into_slice(offset, bytemuck::bytes_of_mut(&mut string))?;
+        Ok(string)
+    }
+
+    /// Registers a value to be read on every [`Emulator::update`], returning a handle to its last two values.
+    ///
+    /// See [`watch`] for details.
+    pub fn watch<T: Pod>(&mut self, offset: u32) -> Option<Watch<T>> {
+        self.watches_mut().add(offset, |emulator, offset, buf| store(buf, emulator.read::<T>(offset)))
+    }
+}
+
+/// A pointer to a value stored in the PS1's memory, which can be used as a field of structs read from it.
+pub type Ptr<T> = EmuPtr<Ps1, T>;
+
+impl<T: CheckedBitPattern> Ptr<T> {
+    /// Reads the value this pointer points to, through the global emulator instance.
+    ///
+    /// Null pointers make this method return `Err(Error::NullPointer)`.
+    pub fn read(self) -> Result<T, Error> {
+        read_global(&STATE, |emulator| self.read_from(emulator))
+    }
+
+    /// Reads the value this pointer points to, through the provided emulator.
+    ///
+    /// Null pointers make this method return `Err(Error::NullPointer)`.
+    pub fn read_from<P: MemorySource>(self, emulator: &Emulator<P>) -> Result<T, Error> {
+        if self.is_null() {
+            return Err(Error::NullPointer { address: 0 })
         }
 
-        game.wram_base.is_some()
+        emulator.read(self.address())
+    }
+}
+
+/// A value stored in the PS1's memory, read again on every update.
+pub type Watch<T> = crate::shared::Watch<Ps1, T>;
+
+impl<T: Pod> Watch<T> {
+    /// Returns the values read by the last two calls to [`update`].
+    ///
+    /// Returns `None` if the last read failed, or if no emulator is attached. Both values are the same
+    /// after the first successful read, as well as after the emulator has been attached again or the RAM has moved.
+    pub fn pair(self) -> Option<Pair<T>> {
+        query_global(&STATE, |emulator| emulator.watches().pair(self)).flatten()
+    }
+
+    /// Returns the values read by the last two updates of the provided emulator.
+    ///
+    /// See [`Watch::pair`] for details.
+    pub fn pair_from<P: MemorySource>(self, emulator: &Emulator<P>) -> Option<Pair<T>> {
+        emulator.watches().pair(self)
     }
 }
 
 /// Calls the internal routines needed in order to hook to the target emulator and find the address of the emulated RAM.
-/// 
+///
 /// Returns true if successful, false otherwise.
-/// 
+///
 /// Supported emulators are:
 /// - ePSXe
 /// - pSX
@@ -103,52 +329,285 @@ impl State {
 /// - Retroarch (supported cores: Beetle-PSX, Swanstation, PCSX ReARMed)
 /// - PCSX-redux
 /// - XEBRA
+///
+/// This is a thin wrapper around a global [`Emulator`] instance.
 pub fn update() -> bool {
-    let state = &mut STATE.lock();
-    state.init()
+    update_global(&STATE, |emulator| emulator.set_retroarch_cores(*RETROARCH_CORES.lock()))
+}
+
+/// Returns the result of the last [`update`], telling apart a missing emulator,
+/// an unsupported core and emulated RAM that has yet to be found.
+pub fn status() -> AttachStatus<EmulatorType> {
+    status_global(&STATE)
+}
+
+/// Sets how often [`update`] looks for the emulated RAM while it can't be found.
+///
+/// By default, [`RescanPolicy::DEFAULT`] is used.
+pub fn set_rescan_policy(policy: RescanPolicy) {
+    set_rescan_policy_global(&STATE, policy)
+}
+
+/// Restricts which emulators [`update`] can attach to, and sets the order they are tried in.
+///
+/// If the emulator currently attached is not allowed anymore, it gets detached.
+pub fn set_emulator_selection(selection: Selection<EmulatorType>) {
+    set_selection_global(&STATE, selection)
+}
+
+/// Sets which process [`update`] attaches to, when several supported emulators are running.
+///
+/// By default, [`AttachPolicy::FirstFound`] is used.
+pub fn set_attach_policy(policy: AttachPolicy) {
+    set_attach_policy_global(&STATE, policy)
+}
+
+/// Lists every supported emulator currently running, along with its process ID.
+///
+/// Any of them can be attached to through [`set_attach_policy`], using [`AttachPolicy::Pid`].
+pub fn candidates() -> impl Iterator<Item = Candidate<EmulatorType>> {
+    Emulator::candidates(&Selection::any())
+}
+
+/// Base addresses of the segments the RAM is mirrored in.
+const KUSEG: u32 = 0x00000000;
+const KSEG0: u32 = 0x80000000;
+const KSEG1: u32 = 0xA0000000;
+
+/// Size of the RAM on retail consoles, and on dev kits.
+const RAM_SIZE: u32 = 0x200000;
+const DEV_KIT_RAM_SIZE: u32 = 0x800000;
+
+/// Guest address of the scratchpad, the 1KB of fast memory inside the CPU.
+const SCRATCHPAD: u32 = 0x1F800000;
+const SCRATCHPAD_SIZE: u32 = 0x400;
+
+/// Guest address of the BIOS, in KSEG1, and its size.
+const BIOS: u32 = 0xBFC00000;
+const BIOS_SIZE: u32 = 0x80000;
+
+/// The whole RAM of a retail PS1, as a range to be passed to [`enable_snapshot`].
+pub const WHOLE_RAM: (u32, usize) = (0x80000000, 0x200000);
+
+/// Serves every read from a local copy of the provided guest ranges, taken once per [`update`].
+///
+/// Each range is an address, in any of the formats accepted by [`read`], along with its length in bytes.
+/// Ranges are copied one after the other into `buf`, which can be obtained from a
+/// [`SnapshotBuffer`](crate::shared::SnapshotBuffer). Reads that aren't entirely contained in a single range,
+/// as well as ranges that don't fit in the buffer, keep being performed on the emulator.
+///
+/// This is meant for auto splitters reading many values on each tick, which then need a single read per range.
+/// Passing [`WHOLE_RAM`] copies the whole 2MB of RAM on each update.
+/// The snapshot is kept across emulators being attached and detached.
+pub fn enable_snapshot(buf: &'static mut [u8], ranges: &[(u32, usize)]) {
+    enable_snapshot_global(&STATE, buf, ranges)
+}
+
+/// Goes back to reading every value from the emulator, returning the buffer used by the snapshot.
+pub fn disable_snapshot() -> Option<&'static mut [u8]> {
+    disable_snapshot_global(&STATE)
+}
+
+/// Copies the guest ranges into the snapshot again, outside of [`update`].
+///
+/// Returns false if no snapshot is enabled, or if it could not be refreshed.
+pub fn refresh_snapshot() -> bool {
+    refresh_snapshot_global(&STATE)
+}
+
+/// Discards the content of the snapshot, so values are read from the emulator until the next [`update`] or [`refresh_snapshot`].
+pub fn invalidate_snapshot() {
+    invalidate_snapshot_global(&STATE)
+}
+
+/// Restricts which Retroarch cores [`update`] can hook to, and sets the order they are tried in.
+pub fn set_retroarch_cores(cores: Selection<RetroarchCore>) {
+    *RETROARCH_CORES.lock() = cores;
+    configure_global(&STATE, |emulator| emulator.set_retroarch_cores(cores))
+}
+
+/// Returns the Retroarch core hooked to by [`update`], if a supported one is loaded.
+///
+/// The emulator itself is reported by [`status`].
+pub fn retroarch_core() -> Option<RetroarchCore> {
+    query_global(&STATE, |emulator| emulator.retroarch_core()).flatten()
+}
+
+/// Returns the serial number and region of the running game, such as `SLUS-01234` for `cdrom:\SLUS_012.34;1`.
+///
+/// The serial comes from the path of the executable booted by the BIOS, which the kernel keeps in the first 64KB
+/// of RAM. As that whole area is scanned on every call, this is meant to be called when attaching or after a reset.
+/// Returns `None` while no game has been booted, or if the executable isn't named after a serial number.
+pub fn game_id() -> Option<GameId> {
+    query_global(&STATE, |emulator| emulator.game_id()).flatten()
+}
+
+/// Lists the regions of the emulated memory, along with the addresses they are stored at inside the emulator.
+///
+/// This is meant for advanced uses, such as asr's own pointer paths or signature scans. Nothing is listed
+/// until [`update`] has found the emulated RAM.
+pub fn memory_domains() -> impl Iterator<Item = MemoryDomain> {
+    memory_domains_global(&STATE)
 }
 
 /// Reads any value from the emulated RAM.
-/// 
-/// In PS1, memory addresses are usually mapped at fixed locations starting from `0x80000000`,
-/// and is the way many emulators, as well as the GameShark on original hardware, access memory.
-/// 
-/// For this reason, this method will automatically convert offsets provided in such format.
-/// For example providing an offset of `0x1234` or `0x80001234` will return the same value.
-/// 
-/// Providing any offset outside the range of the PS1's RAM will return `Err()`.
+///
+/// Offsets can be provided as-is, or in the mapped forms used by games and the GameShark: `0x1234`, `0x80001234`
+/// and `0xA0001234` all return the same value. The RAM is 2MB long, or 8MB when the emulator is set to emulate
+/// a dev kit and the backend detects it, as reported by [`memory_domains`].
+///
+/// The scratchpad can be read at `0x1F800000` or `0x9F800000`, and the BIOS at `0xBFC00000`, once the backend has
+/// found them (see [`Ram`]). Until then they return `Err(Error::Unavailable)`, while other offsets return `Err(Error::OutOfRange)`.
 pub fn read<T: CheckedBitPattern>(offset: u32) -> Result<T, Error> {
-    if (offset > 0x1FFFFF && offset < 0x80000000) || offset > 0x801FFFFF {
-        return Err(Error)
-    }; 
+    read_global(&STATE, |emulator| emulator.read(offset))
+}
+
+/// Reads any value from the BIOS, at an offset from its start.
+///
+/// The BIOS is 512KB long, and can also be read through [`read`] at `0xBFC00000`. Its build date is stored at offset
+/// `0x100`, followed by its version string, which identify the BIOS in use. Returns `Err(Error::Unavailable)` if the
+/// backend hasn't found the BIOS (see [`Ram`]), and `Err(Error::OutOfRange)` for offsets past its end.
+pub fn read_bios<T: CheckedBitPattern>(offset: u32) -> Result<T, Error> {
+    read_global(&STATE, |emulator| emulator.read_bios(offset))
+}
+
+/// Reads the event control block found at `index` in the kernel's event table.
+///
+/// Events are kept by the kernel in the first 64KB of RAM, independently of the game, and their [`Event::status`]
+/// changes as the hardware they are opened for, such as the CD-ROM, triggers them.
+///
+/// Returns `Err(Error::NullPointer)` until the kernel has set up its tables, and `Err(Error::OutOfRange)`
+/// if `index` is past the end of the table.
+pub fn event(index: u32) -> Result<Event, Error> {
+    read_global(&STATE, |emulator| emulator.event(index))
+}
+
+/// Reads the control block of the current thread, as kept by the kernel in the first 64KB of RAM.
+///
+/// Returns `Err(Error::NullPointer)` until the kernel has set up its tables.
+pub fn current_thread() -> Result<Thread, Error> {
+    read_global(&STATE, |emulator| emulator.current_thread())
+}
+
+/// Follows a path of pointers stored in the emulated RAM, then reads the value it leads to.
+///
+/// Starting from `base`, each offset but the last one is added to the current address and the pointer
+/// stored there is read. The last offset is added to the final pointer, and the value is read from there.
+/// Pointers can be stored either as `0x80xxxxxx` addresses or as plain offsets, as accepted by [`read`].
+///
+/// Null pointers, which are common while games are loading, make this method return `Err(Error::NullPointer)`,
+/// while pointers outside of the emulated RAM make it return `Err(Error::OutOfRange)`.
+pub fn read_pointer_path<T: CheckedBitPattern>(base: u32, offsets: &[u32]) -> Result<T, Error> {
+    read_global(&STATE, |emulator| emulator.read_pointer_path(base, offsets))
+}
+
+/// Fills a slice with values read from the emulated RAM, in a single read.
+///
+/// Offsets are accepted in the same formats as [`read`]. The whole slice has to fit inside the PS1's RAM,
+/// otherwise this method will immediately return `Err(Error::OutOfRange)` without reading anything.
+pub fn read_into_slice<T: Pod>(offset: u32, slice: &mut [T]) -> Result<(), Error> {
+    read_global(&STATE, |emulator| emulator.read_into_slice(offset, slice))
+}
+
+/// Reads an array of values from the emulated RAM, in a single read.
+///
+/// See [`read_into_slice`] for details about the accepted offsets.
+pub fn read_array<T: Pod, const N: usize>(offset: u32) -> Result<[T; N], Error> {
+    read_global(&STATE, |emulator| emulator.read_array(offset))
+}
 
-    let state = STATE.lock();
+/// Reads a string of up to `N` bytes from the emulated RAM, in a single read.
+///
+/// The string ends at the first NUL byte, or after `N` bytes for fixed-length fields that fill all of them.
+/// Japanese games usually store their text in Shift-JIS, which can be decoded with `shared::ShiftJis`
+/// when the `shift-jis` feature is enabled.
+///
+/// Offsets are accepted in the same formats as [`read`], and the whole string has to fit inside the PS1's RAM.
+pub fn read_string<const N: usize>(offset: u32) -> Result<ArrayCString<N>, Error> {
+    read_global(&STATE, |emulator| emulator.read_string(offset))
+}
 
-    let Some(proc) = &state.proc else {
-        return Err(Error)
-    };
+/// Registers a value to be read on every [`update`], replacing the need for a hand-made [`asr::watcher::Watcher`].
+///
+/// The value is read in the same way as [`read`], and the returned handle gives access to the values read
+/// during the last two updates, along with the helpers of [`Pair`]. Registered values are kept across emulators
+/// being attached and detached, but what has been read so far is discarded, as it is when the RAM moves.
+///
+/// Returns `None` if [`MAX_WATCHES`](crate::shared::MAX_WATCHES) values are already registered,
+/// or if the value is larger than [`MAX_WATCH_SIZE`](crate::shared::MAX_WATCH_SIZE).
+pub fn watch<T: Pod>(offset: u32) -> Option<Watch<T>> {
+    watches_global(&STATE, |watches| watches.add(offset, |emulator: &Emulator, offset, buf| store(buf, emulator.read::<T>(offset))))
+}
 
-    let Some(wram) = &proc.wram_base else {
-        return Err(Error)
-    };
+/// Stops reading a value registered with [`watch`].
+pub fn unwatch<T: 'static>(watch: Watch<T>) {
+    watches_global(&STATE, |watches| watches.remove(watch))
+}
 
-    const WRAMB: u32 = 0x80000000;
+/// Stops reading every value registered with [`watch`].
+pub fn clear_watches() {
+    watches_global(&STATE, |watches| watches.clear())
+}
 
-    let mut offsetx = offset;
-    
-    if offsetx >= WRAMB {
-        offsetx -= WRAMB
+/// Emulator-specific data, such as pointers cached while looking for the emulated RAM.
+pub enum Backend {
+    Epsxe(epsxe::Epsxe),
+    PsxFin(psxfin::PsxFin),
+    Duckstation(duckstation::Duckstation),
+    Retroarch(retroarch::Retroarch),
+    PcsxRedux(pcsx_redux::PcsxRedux),
+    Xebra(xebra::Xebra),
+}
+
+impl EmulatorBackend<Ps1> for Backend {
+    fn find_ram<P: MemorySource>(&mut self, proc: &P) -> Option<Ram> {
+        match self {
+            Self::Epsxe(backend) => backend.find_ram(proc),
+            Self::PsxFin(backend) => backend.find_ram(proc),
+            Self::Duckstation(backend) => backend.find_ram(proc),
+            Self::Retroarch(backend) => backend.find_ram(proc),
+            Self::PcsxRedux(backend) => backend.find_ram(proc),
+            Self::Xebra(backend) => backend.find_ram(proc),
+        }
     }
 
-    if let Ok(value) = proc.emulator_process.read(Address(wram.0 + offsetx as u64)) {
-        Ok(value)
-    } else {
-        Err(Error)
+    fn keep_alive<P: MemorySource>(&mut self, proc: &P, ram: &mut Ram) -> bool {
+        match self {
+            Self::Epsxe(backend) => backend.keep_alive(proc, ram),
+            Self::PsxFin(backend) => backend.keep_alive(proc, ram),
+            Self::Duckstation(backend) => backend.keep_alive(proc, ram),
+            Self::Retroarch(backend) => backend.keep_alive(proc, ram),
+            Self::PcsxRedux(backend) => backend.keep_alive(proc, ram),
+            Self::Xebra(backend) => backend.keep_alive(proc, ram),
+        }
+    }
+
+    fn core_loaded<P: MemorySource>(&self, proc: &P) -> bool {
+        match self {
+            Self::Epsxe(backend) => backend.core_loaded(proc),
+            Self::PsxFin(backend) => backend.core_loaded(proc),
+            Self::Duckstation(backend) => backend.core_loaded(proc),
+            Self::Retroarch(backend) => backend.core_loaded(proc),
+            Self::PcsxRedux(backend) => backend.core_loaded(proc),
+            Self::Xebra(backend) => backend.core_loaded(proc),
+        }
+    }
+
+    fn core_address<P: MemorySource>(&self, proc: &P) -> Option<Address> {
+        match self {
+            Self::Epsxe(backend) => backend.core_address(proc),
+            Self::PsxFin(backend) => backend.core_address(proc),
+            Self::Duckstation(backend) => backend.core_address(proc),
+            Self::Retroarch(backend) => backend.core_address(proc),
+            Self::PcsxRedux(backend) => backend.core_address(proc),
+            Self::Xebra(backend) => backend.core_address(proc),
+        }
     }
 }
 
+/// The PS1 emulators supported by this crate.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-enum Emulator {
+pub enum EmulatorType {
     Epsxe,
     PsxFin,
     Duckstation,
@@ -157,12 +616,20 @@ enum Emulator {
     Xebra,
 }
 
-const PROCESS_NAMES: [(&str, Emulator); 7] = [
-    ("ePSXe.exe", Emulator::Epsxe),
-    ("psxfin.exe", Emulator::PsxFin),
-    ("duckstation-qt-x64-ReleaseLTCG.exe", Emulator::Duckstation),
-    ("duckstation-nogui-x64-ReleaseLTCG.exe", Emulator::Duckstation),
-    ("retroarch.exe", Emulator::Retroarch),
-    ("pcsx-redux.main", Emulator::PcsxRedux),
-    ("XEBRA.EXE", Emulator::Xebra),
-];
\ No newline at end of file
+/// The Retroarch cores supported by this crate for the PS1.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RetroarchCore {
+    BeetlePsxHw,
+    BeetlePsx,
+    SwanStation,
+    PcsxRearmed,
+}
+
+const PROCESS_NAMES: [(NamePattern, EmulatorType); 6] = [
+    (NamePattern::new("ePSXe{.exe,}"), EmulatorType::Epsxe),
+    (NamePattern::new("psxfin{.exe,}"), EmulatorType::PsxFin),
+    (NamePattern::new("duckstation-{qt,nogui}{-x64-ReleaseLTCG.exe,,.AppImage}"), EmulatorType::Duckstation),
+    (NamePattern::new("retroarch{.exe,}"), EmulatorType::Retroarch),
+    (NamePattern::new("pcsx-redux{.main,.exe,}"), EmulatorType::PcsxRedux),
+    (NamePattern::new("XEBRA{.EXE,}"), EmulatorType::Xebra),
+];
@@ -1,39 +1,57 @@
-use asr::{Address, signature::Signature, sync::Mutex};
+use asr::{Address, signature::Signature};
+use crate::shared::{EmulatorBackend, MemorySource, main_module};
+use super::{Ram, RAM_SIZE, DEV_KIT_RAM_SIZE, regions::Regions};
 
-static STATICDATA: Mutex<StaticData> = Mutex::new(StaticData {
-    addr: Address(0),
-});
-
-struct StaticData {
+#[derive(Default)]
+pub struct Duckstation {
     addr: Address,
+    regions: Regions,
 }
 
-pub fn duckstation(game: &super::ProcessInfo) -> Option<Address> {
-    const SIG: Signature<8> = Signature::new("48 89 0D ?? ?? ?? ?? B8");
-    let proc = &game.emulator_process;
+impl EmulatorBackend<super::Ps1> for Duckstation {
+    fn find_ram<P: MemorySource>(&mut self, proc: &P) -> Option<Ram> {
+        const SIG: Signature<8> = Signature::new("48 89 0D ?? ?? ?? ?? B8");
 
-    let (Some(main_module_address), Some(main_module_size)) = super::PROCESS_NAMES.iter()
-        .filter(|p| p.1 == super::Emulator::Duckstation)
-        .map(|m| (proc.get_module_address(m.0).ok(), proc.get_module_size(m.0).ok()))
-        .find(|m| m.0.is_some() && m.1.is_some())? else { return None };
+        let (main_module_name, main_module_address) = main_module::<super::Ps1, _>(proc, super::EmulatorType::Duckstation)?;
+        let main_module_size = proc.module_size(&main_module_name)?;
 
-    let addr = SIG.scan_process_range(proc, main_module_address, main_module_size)?.0 as i64 + 3;
-    let ptr = addr + 0x4 + proc.read::<i32>(Address(addr as u64)).ok()? as i64;
+        let addr = proc.scan_signature(&SIG, main_module_address, main_module_size)?.0 as i64 + 3;
+        let ptr = addr + 0x4 + proc.read::<i32>(Address(addr as u64))? as i64;
 
-    let mut static_data = STATICDATA.lock();
-    static_data.addr = Address(ptr as u64);
-    let wram = proc.read::<u64>(static_data.addr).ok()?;
+        self.addr = Address(ptr as u64);
+        let wram = proc.read::<u64>(self.addr)?;
 
-    Some(Address(wram))
-}
+        let mut ram = Ram { size: self.ram_size(proc), ..Ram::new(Address(wram)) };
+        // The BIOS is declared along with the RAM, either as an array or as a pointer to it
+        self.regions.locate(proc, &mut ram, self.addr, true, false);
+        Some(ram)
+    }
 
-pub fn keep_alive(game: &mut super::ProcessInfo) -> bool {
-    let static_data = STATICDATA.lock();
+    fn keep_alive<P: MemorySource>(&mut self, proc: &P, ram: &mut Ram) -> bool {
+        if let Some(addr) = proc.read::<u64>(self.addr) {
+            ram.base = Address(addr);
+            ram.size = self.ram_size(proc);
+            self.regions.update(proc, ram);
+            true
+        } else {
+            false
+        }
+    }
+}
 
-    if let Ok(addr) = game.emulator_process.read::<u64>(static_data.addr) {
-        game.wram_base = Some(Address(addr));
-        true
-    } else {
-        false
+impl Duckstation {
+    /// Returns the size of the RAM, which is 8MB when the dev kit RAM is enabled.
+    ///
+    /// The RAM pointer is declared right before the size and mask of the RAM, although their exact position
+    /// depends on the version, so the first consecutive pair that fits is looked for. Defaults to 2MB.
+    fn ram_size<P: MemorySource>(&self, proc: &P) -> u32 {
+        let Some(words) = proc.read::<[u32; 8]>(Address(self.addr.0 + 8)) else { return RAM_SIZE };
+
+        words.windows(2)
+            .find_map(|pair| match *pair {
+                [size, mask] if (size == RAM_SIZE || size == DEV_KIT_RAM_SIZE) && mask == size - 1 => Some(size),
+                _ => None,
+            })
+            .unwrap_or(RAM_SIZE)
     }
-}
\ No newline at end of file
+}
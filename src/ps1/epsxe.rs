@@ -1,21 +1,27 @@
 use asr::Address;
 use asr::signature::Signature;
+use crate::shared::{EmulatorBackend, MemorySource, main_module};
+use super::{Ram, regions};
 
-pub fn epsxe(game: &super::ProcessInfo) -> Option<Address> {
-    const SIG: Signature<5> = Signature::new("C1 E1 10 8D 89");
-    let proc = &game.emulator_process;  
-    
-    let (Some(main_module_address), Some(main_module_size)) = super::PROCESS_NAMES.iter()
-        .filter(|p| p.1 == super::Emulator::Epsxe)
-        .map(|m| (proc.get_module_address(m.0).ok(), proc.get_module_size(m.0).ok()))
-        .find(|m| m.0.is_some() && m.1.is_some())? else { return None };
+#[derive(Default)]
+pub struct Epsxe;
 
-    
-    let ptr = SIG.scan_process_range(proc, main_module_address, main_module_size)?.0 + 5;
-    let ptr = proc.read::<u32>(Address(ptr)).ok()? as u64;
-    Some(Address(ptr))
-}
+impl EmulatorBackend<super::Ps1> for Epsxe {
+    fn find_ram<P: MemorySource>(&mut self, proc: &P) -> Option<Ram> {
+        const SIG: Signature<5> = Signature::new("C1 E1 10 8D 89");
+        
+        let (main_module_name, main_module_address) = main_module::<super::Ps1, _>(proc, super::EmulatorType::Epsxe)?;
+        let main_module_size = proc.module_size(&main_module_name)?;
+
+        
+        let ptr = proc.scan_signature(&SIG, main_module_address, main_module_size)?.0 + 5;
+        let ptr = proc.read::<u32>(Address(ptr))? as u64;
+        let mut ram = Ram::new(Address(ptr));
+        regions::locate_near_ram(proc, &mut ram);
+        Some(ram)
+    }
 
-pub fn keep_alive() -> bool {
-    true
-}
\ No newline at end of file
+    fn keep_alive<P: MemorySource>(&mut self, _proc: &P, _ram: &mut Ram) -> bool {
+        true
+    }
+}
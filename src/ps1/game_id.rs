@@ -0,0 +1,81 @@
+use core::fmt;
+
+/// Size of the kernel area at the start of the RAM, where the BIOS keeps the path of the boot executable.
+pub(super) const KERNEL_SIZE: u32 = 0x10000;
+
+/// Maximum length of a boot path, so that chunks read from the kernel area can overlap by that much.
+pub(super) const MAX_PATH_LEN: usize = 0x40;
+
+/// Serial number and region of a PS1 game, as found in the name of its boot executable.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct GameId {
+    /// Always ASCII, such as `SLUS-01234`.
+    serial: [u8; 10],
+}
+
+impl GameId {
+    /// Parses an executable name, such as `SLUS_012.34`.
+    fn parse(name: &[u8]) -> Option<Self> {
+        let &[a, b, c, d, b'_' | b'-', e, f, g, b'.', h, i] = name else { return None };
+
+        let letters = [a, b, c, d].map(|c| c.to_ascii_uppercase());
+        let digits = [e, f, g, h, i];
+
+        if !letters.iter().all(u8::is_ascii_uppercase) || !digits.iter().all(u8::is_ascii_digit) {
+            return None
+        }
+
+        let [a, b, c, d] = letters;
+        Some(Self { serial: [a, b, c, d, b'-', e, f, g, h, i] })
+    }
+
+    /// Looks for a boot path, such as `cdrom:\SLUS_012.34;1`, inside a chunk of the kernel area.
+    pub(super) fn find(bytes: &[u8]) -> Option<Self> {
+        const DEVICE: &[u8] = b"cdrom:";
+
+        bytes.windows(DEVICE.len()).enumerate()
+            .filter(|(_, window)| window.eq_ignore_ascii_case(DEVICE))
+            .find_map(|(i, _)| {
+                let path = &bytes[i + DEVICE.len()..];
+                let len = path.iter().position(|&c| c == b';' || !c.is_ascii_graphic()).unwrap_or(path.len());
+                let name = path[..len].rsplit(|&c| c == b'\\' || c == b'/').next()?;
+
+                Self::parse(name)
+            })
+    }
+
+    /// Returns the serial number, such as `SLUS-01234`.
+    pub fn serial(&self) -> &str {
+        core::str::from_utf8(&self.serial).unwrap_or_default()
+    }
+
+    /// Returns the region the game has been released in, guessed from the prefix of its serial number.
+    pub fn region(&self) -> Option<Region> {
+        match &self.serial[2..4] {
+            b"US" => Some(Region::NtscU),
+            b"PS" | b"PM" | b"PD" | b"PX" => Some(Region::NtscJ),
+            b"ES" | b"ED" => Some(Region::Pal),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GameId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.serial())
+    }
+}
+
+impl fmt::Debug for GameId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "GameId({})", self.serial())
+    }
+}
+
+/// The regions PS1 games have been released in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Region {
+    NtscU,
+    NtscJ,
+    Pal,
+}
@@ -0,0 +1,65 @@
+use bytemuck::{Pod, Zeroable};
+
+/// Address of the kernel's table of tables, which lists its arrays of control blocks along with their size.
+pub(super) const TABLE_OF_TABLES: u32 = 0x100;
+
+/// Offsets, inside the table of tables, of the entries pointing to the process and event control blocks.
+pub(super) const PROCESSES: u32 = 0x08;
+pub(super) const EVENTS: u32 = 0x20;
+
+/// An event control block, as registered by `OpenEvent` and kept by the kernel inside its event table.
+///
+/// The BIOS and most games open events for the CD-ROM, the memory cards and the root counters,
+/// so watching their status is a way to detect loads which doesn't depend on the game itself.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct Event {
+    /// Class of the event, such as `0xF0000003` for the CD-ROM.
+    pub class: u32,
+    /// One of [`Event::FREE`], [`Event::DISABLED`], [`Event::ENABLED`] or [`Event::READY`].
+    pub status: u32,
+    /// Which of the events of the class this is.
+    pub spec: u32,
+    /// `0x1000` if the event calls `handler` when triggered, or `0x2000` if it only becomes ready.
+    pub mode: u32,
+    /// Address of the function called when the event is triggered.
+    pub handler: u32,
+    _reserved: [u32; 2],
+}
+
+impl Event {
+    /// The slot is not in use.
+    pub const FREE: u32 = 0x0000;
+    /// The event is open, but won't be triggered until it's enabled.
+    pub const DISABLED: u32 = 0x1000;
+    /// The event is enabled and busy, waiting to be triggered.
+    pub const ENABLED: u32 = 0x2000;
+    /// The event is enabled and ready: it has been triggered, and not acknowledged yet.
+    pub const READY: u32 = 0x4000;
+}
+
+/// A thread control block, which holds the state of a thread while it's not running.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct Thread {
+    /// `0x1000` if the slot is free, or `0x4000` if it's in use.
+    pub status: u32,
+    _reserved: u32,
+    /// General purpose registers, starting from `r0`.
+    pub registers: [u32; 32],
+    pub pc: u32,
+    pub hi: u32,
+    pub lo: u32,
+    /// Status register of the coprocessor 0.
+    pub sr: u32,
+    /// Cause register of the coprocessor 0.
+    pub cause: u32,
+    _reserved_2: [u32; 9],
+}
+
+// SAFETY: both structs are `repr(C)` and only made of `u32`s, so they have no padding
+// and every bit pattern is valid.
+unsafe impl Zeroable for Event {}
+unsafe impl Pod for Event {}
+unsafe impl Zeroable for Thread {}
+unsafe impl Pod for Thread {}
@@ -1,74 +1,102 @@
-use asr::{Address, signature::Signature, MemoryRangeFlags, sync::Mutex};
+use asr::{Address, signature::Signature, MemoryRangeFlags};
 use crate::shared::check_for_64_bit;
+use crate::shared::{EmulatorBackend, MemorySource, main_module};
+use super::{Ram, RAM_SIZE, DEV_KIT_RAM_SIZE, regions};
 
-static STATICDATA: Mutex<StaticData> = Mutex::new(StaticData {
-    is_64_bit: false,
-    addr_base: 0,
-    addr: 0,
-});
-
-struct StaticData {
+#[derive(Default)]
+pub struct PcsxRedux {
     is_64_bit: bool,
     addr_base: u64,
     addr: u64,
+    memory: u64,
 }
 
-pub fn pcsx_redux(game: &super::ProcessInfo) -> Option<Address> {
-    let proc = &game.emulator_process;
+impl EmulatorBackend<super::Ps1> for PcsxRedux {
+    fn find_ram<P: MemorySource>(&mut self, proc: &P) -> Option<Ram> {
+        let (main_module_name, main_module_address) = main_module::<super::Ps1, _>(proc, super::EmulatorType::PcsxRedux)?;
+        let main_module_size = proc.module_size(&main_module_name)?;
+        
+        let is_64_bit = check_for_64_bit(proc, main_module_address);
+        self.is_64_bit = is_64_bit;
 
-    let (Some(main_module_address), Some(main_module_size)) = super::PROCESS_NAMES.iter()
-        .filter(|p| p.1 == super::Emulator::PcsxRedux)
-        .map(|m| (proc.get_module_address(m.0).ok(), proc.get_module_size(m.0).ok()))
-        .find(|m| m.0.is_some() && m.1.is_some())? else { return None };
-    
-    let mut static_data = STATICDATA.lock();
-    let is_64_bit = check_for_64_bit(proc, main_module_address);
-    static_data.is_64_bit = is_64_bit;
+        if is_64_bit {
+            const SIG_BASE: Signature<25> = Signature::new("48 B9 ?? ?? ?? ?? ?? ?? ?? ?? E8 ?? ?? ?? ?? C7 85 ?? ?? ?? ?? 00 00 00 00");
+            const SIG_OFFSET: Signature<9> = Signature::new("89 D1 C1 E9 10 48 8B ?? ??");
 
-    if is_64_bit {
-        const SIG_BASE: Signature<25> = Signature::new("48 B9 ?? ?? ?? ?? ?? ?? ?? ?? E8 ?? ?? ?? ?? C7 85 ?? ?? ?? ?? 00 00 00 00");
-        const SIG_OFFSET: Signature<9> = Signature::new("89 D1 C1 E9 10 48 8B ?? ??");
+            let addr_base = proc.scan_signature(&SIG_BASE, main_module_address, main_module_size)?.0 + 2;
+            self.addr_base = addr_base;
+            let addr_base = proc.read::<u64>(Address(addr_base))?;
+            self.addr = addr_base;
 
-        let addr_base = SIG_BASE.scan_process_range(proc, main_module_address, main_module_size)?.0 + 2;
-        static_data.addr_base = addr_base;
-        let addr_base = proc.read::<u64>(Address(addr_base)).ok()?;
-        static_data.addr = addr_base;
+            let offset = proc.scan_signature(&SIG_OFFSET, main_module_address, main_module_size)?.0 + 8;
+            let offset = proc.read::<u8>(Address(offset))? as u64;
+            
+            // The memory object starts with the pointers to each region: the RAM, the expansion region 1,
+            // the BIOS and the hardware registers, whose first kilobyte is the scratchpad
+            let memory = proc.read::<u64>(Address(addr_base + offset))?;
+            self.memory = memory;
+            let [wram, _, bios, hardware] = proc.read::<[u64; 4]>(Address(memory))?;
 
-        let offset = SIG_OFFSET.scan_process_range(proc, main_module_address, main_module_size)?.0 + 8;
-        let offset = proc.read::<u8>(Address(offset)).ok()? as u64;
-        
-        let addr = proc.read::<u64>(Address(addr_base + offset)).ok()?;
-        let addr = proc.read::<u64>(Address(addr)).ok()?;
-        
-        Some(Address(addr))
-    } else {
-        const SIG: Signature<18> = Signature::new("8B 3D 20 ?? ?? ?? 0F B7 D3 8B 04 95 ?? ?? ?? ?? 21 05");
+            Some(Ram {
+                size: self.ram_size(proc, wram),
+                scratchpad: (hardware != 0).then_some(Address(hardware)),
+                bios: (bios != 0).then_some(Address(bios)),
+                ..Ram::new(Address(wram))
+            })
+        } else {
+            const SIG: Signature<18> = Signature::new("8B 3D 20 ?? ?? ?? 0F B7 D3 8B 04 95 ?? ?? ?? ?? 21 05");
 
-        let addr_base = proc.memory_ranges()
-            .filter(|m| m.flags().unwrap_or_default().contains(MemoryRangeFlags::WRITE))
-            .find_map(|m| SIG.scan_process_range(proc, m.address().unwrap_or_else(|_| Address(0)), m.size().unwrap_or_default()))?
-            .0 + 2;
-        
-        static_data.addr_base = addr_base;
+            let addr_base = proc.memory_ranges()
+                .filter(|m| m.flags.contains(MemoryRangeFlags::WRITE))
+                .find_map(|m| proc.scan_signature(&SIG, m.address, m.size))?
+                .0 + 2;
+            
+            self.addr_base = addr_base;
 
-        let addr = proc.read::<u32>(Address(addr_base)).ok()? as u64;
-        static_data.addr = addr;
+            let addr = proc.read::<u32>(Address(addr_base))? as u64;
+            self.addr = addr;
 
-        Some(Address(addr))
+            let mut ram = Ram::new(Address(addr));
+            regions::locate_near_ram(proc, &mut ram);
+            Some(ram)
+        }
     }
-}
 
-pub fn keep_alive(game: &super::ProcessInfo) -> bool {
-    let static_data = STATICDATA.lock();
-    
-    if static_data.addr_base == 0 {
-        return false
+    fn keep_alive<P: MemorySource>(&mut self, proc: &P, ram: &mut Ram) -> bool {
+        if self.addr_base == 0 {
+            return false
+        }
+
+        if self.is_64_bit {
+            if Some(self.addr) != proc.read::<u64>(Address(self.addr_base)) {
+                return false
+            }
+
+            ram.size = self.ram_size(proc, ram.base.0);
+            true
+        } else {
+            let Some(addr) = proc.read::<u32>(Address(self.addr_base)) else { return false };
+            self.addr == addr as u64
+        }
     }
+}
 
-    if static_data.is_64_bit {
-        Some(static_data.addr) == game.emulator_process.read::<u64>(Address(static_data.addr_base)).ok()
-    } else {
-        let Some(addr) = game.emulator_process.read::<u32>(Address(static_data.addr_base)).ok() else { return false };
-        static_data.addr == addr as u64
+impl PcsxRedux {
+    /// Returns the size of the RAM, which is 8MB when the 8MB setting is enabled.
+    ///
+    /// The region pointers of the memory object are followed by its write and read lookup tables, which map
+    /// each 64KB page of the guest to the host. The page at 2MB only points to the third megabyte of the RAM
+    /// with 8MB, rather than mirroring its start. Defaults to 2MB if neither table can be checked.
+    fn ram_size<P: MemorySource>(&self, proc: &P, wram: u64) -> u32 {
+        let Some(tables) = proc.read::<[u64; 2]>(Address(self.memory + 0x20)) else { return RAM_SIZE };
+        let page = |table: u64, index: u64| proc.read::<u64>(Address(table + index * 8));
+
+        let is_8mb = tables.into_iter().any(|table| {
+            table != 0
+                && page(table, 0) == Some(wram)
+                && page(table, (RAM_SIZE >> 16) as u64) == Some(wram + RAM_SIZE as u64)
+        });
+
+        if is_8mb { DEV_KIT_RAM_SIZE } else { RAM_SIZE }
     }
-}
\ No newline at end of file
+}
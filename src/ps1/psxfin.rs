@@ -1,41 +1,46 @@
 use asr::{Address, signature::Signature};
-
-pub fn psxfin(game: &super::ProcessInfo) -> Option<Address> {
-    const SIG: Signature<9> = Signature::new("8B 15 ?? ?? ?? ?? 8D 34 1A"); // v1.13
-    const SIG_0: Signature<8> = Signature::new("A1 ?? ?? ?? ?? 8D 34 18"); // v1.12
-    const SIG_1: Signature<9> = Signature::new("A1 ?? ?? ?? ?? 8B 7C 24 14"); // v1.5 through v1.11
-    const SIG_2: Signature<8> = Signature::new("A1 ?? ?? ?? ?? 8B 6C 24"); // v1.0 through v1.4
-
-    let proc = &game.emulator_process;
-
-    let (Some(main_module_address), Some(main_module_size)) = super::PROCESS_NAMES.iter()
-        .filter(|p| p.1 == super::Emulator::PsxFin)
-        .map(|m| (proc.get_module_address(m.0).ok(), proc.get_module_size(m.0).ok()))
-        .find(|m| m.0.is_some() && m.1.is_some())? else { return None };
-
-    let mut ptr: u64;
-
-    if let Some(sig) = SIG.scan_process_range(proc, main_module_address, main_module_size) {
-        ptr = proc.read::<u32>(Address(sig.0 + 2)).ok()? as u64;
-    } else if let Some(sig) = SIG_0.scan_process_range(proc, main_module_address, main_module_size) {
-        ptr = proc.read::<u32>(Address(sig.0 + 1)).ok()? as u64;
-    } else if let Some(sig) = SIG_1.scan_process_range(proc, main_module_address, main_module_size) {
-        ptr = proc.read::<u32>(Address(sig.0 + 1)).ok()? as u64;
-    } else if let Some(sig) = SIG_2.scan_process_range(proc, main_module_address, main_module_size) {
-        ptr = proc.read::<u32>(Address(sig.0 + 1)).ok()? as u64;
-    } else {
-        return None
+use crate::shared::{EmulatorBackend, MemorySource, main_module};
+use super::{Ram, regions};
+
+#[derive(Default)]
+pub struct PsxFin;
+
+impl EmulatorBackend<super::Ps1> for PsxFin {
+    fn find_ram<P: MemorySource>(&mut self, proc: &P) -> Option<Ram> {
+        const SIG: Signature<9> = Signature::new("8B 15 ?? ?? ?? ?? 8D 34 1A"); // v1.13
+        const SIG_0: Signature<8> = Signature::new("A1 ?? ?? ?? ?? 8D 34 18"); // v1.12
+        const SIG_1: Signature<9> = Signature::new("A1 ?? ?? ?? ?? 8B 7C 24 14"); // v1.5 through v1.11
+        const SIG_2: Signature<8> = Signature::new("A1 ?? ?? ?? ?? 8B 6C 24"); // v1.0 through v1.4
+
+        let (main_module_name, main_module_address) = main_module::<super::Ps1, _>(proc, super::EmulatorType::PsxFin)?;
+        let main_module_size = proc.module_size(&main_module_name)?;
+
+        let mut ptr: u64;
+
+        if let Some(sig) = proc.scan_signature(&SIG, main_module_address, main_module_size) {
+            ptr = proc.read::<u32>(Address(sig.0 + 2))? as u64;
+        } else if let Some(sig) = proc.scan_signature(&SIG_0, main_module_address, main_module_size) {
+            ptr = proc.read::<u32>(Address(sig.0 + 1))? as u64;
+        } else if let Some(sig) = proc.scan_signature(&SIG_1, main_module_address, main_module_size) {
+            ptr = proc.read::<u32>(Address(sig.0 + 1))? as u64;
+        } else if let Some(sig) = proc.scan_signature(&SIG_2, main_module_address, main_module_size) {
+            ptr = proc.read::<u32>(Address(sig.0 + 1))? as u64;
+        } else {
+            return None
+        }
+
+        ptr = proc.read::<u32>(Address(ptr))? as u64;
+
+        if ptr == 0 {
+            return None
+        }
+
+        let mut ram = Ram::new(Address(ptr));
+        regions::locate_near_ram(proc, &mut ram);
+        Some(ram)
     }
 
-    ptr = proc.read::<u32>(Address(ptr)).ok()? as u64;
-
-    if ptr == 0 {
-        None
-    } else {
-        Some(Address(ptr))
+    fn keep_alive<P: MemorySource>(&mut self, _proc: &P, _ram: &mut Ram) -> bool {
+        true
     }
 }
-
-pub fn keep_alive() -> bool {
-    true
-}
\ No newline at end of file
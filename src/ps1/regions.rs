@@ -0,0 +1,172 @@
+use asr::{Address, MemoryRangeFlags, signature::Signature};
+use crate::shared::{Backoff, MemorySource, RescanPolicy};
+use super::{Ram, SCRATCHPAD_SIZE, BIOS_SIZE};
+
+/// The first memory control registers, at `0x1F801000`, once the BIOS has set them up: the base addresses
+/// of the expansion regions 1 and 2, followed two registers later by the delay and size of the BIOS ROM.
+const MEMORY_CONTROL: Signature<20> = Signature::new("00 00 00 1F 00 20 80 1F ?? ?? ?? ?? ?? ?? ?? ?? 3F 24 13 00");
+
+/// Offset of the memory control registers from the start of the scratchpad.
+const MEMORY_CONTROL_OFFSET: u64 = 0x1000;
+
+/// The reset vector of the BIOSes made by Sony, whose first instructions set up the delay and size of the BIOS ROM.
+const BIOS_RESET: Signature<16> = Signature::new("13 00 08 3C 3F 24 08 35 80 1F 01 3C 10 10 28 AC");
+
+/// Offset of the build date of the BIOS, such as `0x19951204`.
+const BIOS_DATE_OFFSET: u64 = 0x100;
+
+/// How many pointers are checked on each side of the anchor.
+const ANCHOR_SLOTS: u64 = 32;
+
+/// How far from the RAM the memory of the emulators without known pointers to the regions is scanned.
+const NEAR_RAM: u64 = 0x1000000;
+
+/// How often the pointers around the anchor are checked again while a region is missing.
+const RETRY_POLICY: RescanPolicy = RescanPolicy { initial_interval: 60, max_interval: 600 };
+
+/// A variable of the emulator declared along with the pointers to the regions, such as the pointer to the RAM.
+#[derive(Copy, Clone)]
+struct Anchor {
+    address: Address,
+    is_64_bit: bool,
+    scratchpad: bool,
+}
+
+/// Looks for the scratchpad and the BIOS among the pointers an emulator declares next to a known variable.
+///
+/// The pointers are checked again while the regions are missing, as the BIOS may be loaded after the RAM
+/// is allocated, and the hardware registers only match once the BIOS has started.
+pub(super) struct Regions {
+    anchor: Option<Anchor>,
+    backoff: Backoff,
+}
+
+impl Default for Regions {
+    fn default() -> Self {
+        Self { anchor: None, backoff: Backoff::new(RETRY_POLICY) }
+    }
+}
+
+impl Regions {
+    /// Looks for the regions of `ram` among the pointers declared around `anchor`. The scratchpad is only looked for
+    /// if the emulator keeps it at the start of a flat buffer of the hardware registers.
+    pub fn locate<P: MemorySource>(&mut self, proc: &P, ram: &mut Ram, anchor: Address, is_64_bit: bool, scratchpad: bool) {
+        self.anchor = Some(Anchor { address: anchor, is_64_bit, scratchpad });
+        self.backoff.reset();
+        self.update(proc, ram);
+    }
+
+    /// Checks the pointers around the anchor again for the missing regions of `ram`,
+    /// unless the previous check failed too recently.
+    pub fn update<P: MemorySource>(&mut self, proc: &P, ram: &mut Ram) {
+        let Some(anchor) = self.anchor else { return };
+        let scratchpad = anchor.scratchpad && ram.scratchpad.is_none();
+        let bios = ram.bios.is_none();
+
+        if !(scratchpad || bios) || !self.backoff.should_scan() {
+            return
+        }
+
+        if scratchpad {
+            ram.scratchpad = anchor.find(proc, |address| is_scratchpad(proc, address));
+        }
+        if bios {
+            ram.bios = anchor.find(proc, |address| is_bios(proc, address));
+        }
+
+        if ram.bios.is_none() || (scratchpad && ram.scratchpad.is_none()) {
+            self.backoff.failed();
+        }
+    }
+}
+
+impl Anchor {
+    /// Returns the first address accepted by `check`, among the ones the pointers around the anchor point to,
+    /// along with the addresses of the pointers themselves, for emulators declaring a region as an array.
+    fn find<P: MemorySource>(&self, proc: &P, check: impl Fn(Address) -> bool) -> Option<Address> {
+        let size = if self.is_64_bit { 8 } else { 4 };
+
+        (0..=2 * ANCHOR_SLOTS)
+            .filter_map(|slot| (self.address.0 + slot * size).checked_sub(ANCHOR_SLOTS * size))
+            .find_map(|slot| {
+                let pointer = if self.is_64_bit {
+                    proc.read::<u64>(Address(slot))
+                } else {
+                    proc.read::<u32>(Address(slot)).map(u64::from)
+                };
+
+                [pointer?, slot].into_iter().map(Address).find(|&address| address.0 != 0 && check(address))
+            })
+    }
+}
+
+/// Scans the writable memory near the RAM for the regions of `ram`, for the emulators without any known pointer to them.
+///
+/// This is only done once, when hooking to the emulator, so the scratchpad is only found if the BIOS has started by then.
+pub(super) fn locate_near_ram<P: MemorySource>(proc: &P, ram: &mut Ram) {
+    let base = ram.base.0;
+
+    ram.scratchpad = scan_near(proc, base, &MEMORY_CONTROL, |found| {
+        let scratchpad = Address(found.0.checked_sub(MEMORY_CONTROL_OFFSET)?);
+        is_scratchpad(proc, scratchpad).then_some(scratchpad)
+    });
+    ram.bios = scan_near(proc, base, &BIOS_RESET, |found| is_bios(proc, found).then_some(found));
+}
+
+/// Whether `address` holds the hardware registers, starting with the scratchpad.
+///
+/// The memory control registers are expected `0x1000` bytes after it, with the unused area
+/// between the end of the scratchpad and them left zeroed.
+fn is_scratchpad<P: MemorySource>(proc: &P, address: Address) -> bool {
+    let memory_control = Address(address.0 + MEMORY_CONTROL_OFFSET);
+    if proc.scan_signature(&MEMORY_CONTROL, memory_control, 20) != Some(memory_control) {
+        return false
+    }
+
+    let mut unused = [0; (MEMORY_CONTROL_OFFSET - SCRATCHPAD_SIZE as u64) as usize];
+    proc.read_into_buf(Address(address.0 + SCRATCHPAD_SIZE as u64), &mut unused).is_some()
+        && unused.iter().all(|&byte| byte == 0)
+}
+
+/// Whether `address` holds a whole image of a BIOS made by Sony, starting with its reset vector and holding
+/// a build date made of BCD digits. Replacement BIOSes, such as the HLE ones of some emulators, aren't recognized.
+fn is_bios<P: MemorySource>(proc: &P, address: Address) -> bool {
+    if proc.scan_signature(&BIOS_RESET, address, 16) != Some(address)
+        || proc.read::<u8>(Address(address.0 + BIOS_SIZE as u64 - 1)).is_none()
+    {
+        return false
+    }
+
+    let Some(date) = proc.read::<u32>(Address(address.0 + BIOS_DATE_OFFSET)) else { return false };
+    let is_bcd = (0..8).all(|digit| (date >> (digit * 4)) & 0xF <= 9);
+
+    is_bcd && (0x1990..0x2100).contains(&(date >> 16))
+}
+
+/// Scans the writable memory within [`NEAR_RAM`] bytes of the RAM for every match of `signature`,
+/// until `check` accepts one of them.
+fn scan_near<P: MemorySource, const N: usize>(
+    proc: &P,
+    ram: u64,
+    signature: &Signature<N>,
+    check: impl Fn(Address) -> Option<Address>,
+) -> Option<Address> {
+    let (low, high) = (ram.saturating_sub(NEAR_RAM), ram.saturating_add(NEAR_RAM));
+
+    proc.memory_ranges()
+        .filter(|m| m.flags.contains(MemoryRangeFlags::WRITE))
+        .find_map(|region| {
+            let end = (region.address.0 + region.size).min(high);
+            let mut start = region.address.0.max(low);
+
+            while start < end {
+                let found = proc.scan_signature(signature, Address(start), end - start)?;
+                if let Some(address) = check(found) {
+                    return Some(address)
+                }
+                start = found.0 + 1;
+            }
+
+            None
+        })
+}
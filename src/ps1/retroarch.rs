@@ -1,95 +1,108 @@
 use crate::shared::check_for_64_bit;
-use asr::{Address, signature::Signature, sync::Mutex};
+use asr::{Address, signature::Signature};
+use crate::shared::{EmulatorBackend, MemorySource, NamePattern, Selection, main_module};
+use super::{Ram, RetroarchCore, regions::Regions};
 
-static STATICDATA: Mutex<StaticData> = Mutex::new(StaticData {
-    core_addr: Address(0),
-});
+const SUPPORTED_CORES: [(NamePattern, RetroarchCore); 4] = [
+    (NamePattern::new("mednafen_psx_hw_libretro{.dll,.so,.dylib}"), RetroarchCore::BeetlePsxHw),
+    (NamePattern::new("mednafen_psx_libretro{.dll,.so,.dylib}"), RetroarchCore::BeetlePsx),
+    (NamePattern::new("swanstation_libretro{.dll,.so,.dylib}"), RetroarchCore::SwanStation),
+    (NamePattern::new("pcsx_rearmed_libretro{.dll,.so,.dylib}"), RetroarchCore::PcsxRearmed),
+];
 
-struct StaticData {
+#[derive(Default)]
+pub struct Retroarch {
     core_addr: Address,
+    pub(super) cores: Selection<RetroarchCore>,
+    pub(super) core: Option<RetroarchCore>,
+    regions: Regions,
 }
 
-pub fn retroarch(game: &super::ProcessInfo) -> Option<Address> {
-    const SUPPORTED_CORES: [&str; 4] = [
-        "mednafen_psx_hw_libretro.dll",
-        "mednafen_psx_libretro.dll",
-        "swanstation_libretro.dll",
-        "pcsx_rearmed_libretro.dll",
-    ]; 
+impl EmulatorBackend<super::Ps1> for Retroarch {
+    fn find_ram<P: MemorySource>(&mut self, proc: &P) -> Option<Ram> {
+        self.core = None;
+        let (_, main_module_address) = main_module::<super::Ps1, _>(proc, super::EmulatorType::Retroarch)?;
 
-    let proc = &game.emulator_process;
-    let mut static_data = STATICDATA.lock();
+        let is_64_bit = check_for_64_bit(proc, main_module_address);
 
-    let Some(main_module_address) = super::PROCESS_NAMES.iter()
-        .filter(|p| p.1 == super::Emulator::Retroarch)
-        .map(|m| proc.get_module_address(m.0).ok())
-        .find(|m| m.is_some())? else { return None };
+        let (core, (core_name, core_address)) = self.cores.apply(&SUPPORTED_CORES, |c| c.1)
+            .find_map(|&(pattern, core)| Some((core, pattern.find_module(proc)?)))?;
 
-    let is_64_bit = check_for_64_bit(proc, main_module_address);
+        self.core_addr = core_address;
+        self.core = Some(core);
 
-    let (&core, Ok(core_address)) = SUPPORTED_CORES.iter()
-        .map(|m| (m, proc.get_module_address(m)))
-        .find(|m| m.1.is_ok())? else { return None };
+        // Along with the RAM, the address of the variable the pointers to the other regions are declared next to
+        let (base, anchor) = if core == RetroarchCore::BeetlePsxHw || core == RetroarchCore::BeetlePsx {
+            // Mednafen
+            if is_64_bit {
+                const SIG: Signature<14> = Signature::new("48 8B 05 ?? ?? ?? ?? 41 81 E4 FF FF 1F 00");
+                let ptr = proc.scan_signature(&SIG, core_address, proc.module_size(&core_name)?)?.0 + 3;
+                let ptr = ptr as i64 + 0x4 + proc.read::<i32>(Address(ptr))? as i64;
 
-    static_data.core_addr = core_address;
+                let wram = proc.read::<u64>(Address(ptr as u64))?;
+                Some((Address(wram), Address(ptr as u64)))
+            } else {
+                const SIG: Signature<11> = Signature::new("A1 ?? ?? ?? ?? 81 E3 FF FF 1F 00");
+                let ptr = proc.scan_signature(&SIG, core_address, proc.module_size(&core_name)?)?.0 + 1;
+                let ptr = proc.read::<u32>(Address(ptr))? as u64;
 
-    if core == SUPPORTED_CORES[0] || core == SUPPORTED_CORES[1] {
-        // Mednafen
-        if is_64_bit {
-            const SIG: Signature<14> = Signature::new("48 8B 05 ?? ?? ?? ?? 41 81 E4 FF FF 1F 00");
-            let ptr = SIG.scan_process_range(proc, core_address, proc.get_module_size(core).ok()?)?.0 + 3;
-            let ptr = ptr as i64 + 0x4 + proc.read::<i32>(Address(ptr)).ok()? as i64;
-
-            let ptr = proc.read::<u64>(Address(ptr as u64)).ok()?;
-            Some(Address(ptr))
-        } else {
-            const SIG: Signature<11> = Signature::new("A1 ?? ?? ?? ?? 81 E3 FF FF 1F 00");
-            let ptr = SIG.scan_process_range(proc, core_address, proc.get_module_size(core).ok()?)?.0 + 1;
-            let ptr = proc.read::<u32>(Address(ptr)).ok()? as u64;
-
-            let ptr = proc.read::<u32>(Address(ptr)).ok()? as u64;
-            Some(Address(ptr))
-        }
-    } else if core == SUPPORTED_CORES[2] {
-        // Swanstation
-        if is_64_bit {
-            const SIG: Signature<15> = Signature::new("48 89 0D ?? ?? ?? ?? 89 35 ?? ?? ?? ?? 89 3D");
-            let addr = SIG.scan_process_range(proc, core_address, proc.get_module_size(core).ok()?)?.0 as i64 + 3;
-            let ptr = addr + 0x4 + proc.read::<i32>(Address(addr as u64)).ok()? as i64;
+                let wram = proc.read::<u32>(Address(ptr))? as u64;
+                Some((Address(wram), Address(ptr)))
+            }
+        } else if core == RetroarchCore::SwanStation {
+            // Swanstation
+            if is_64_bit {
+                const SIG: Signature<15> = Signature::new("48 89 0D ?? ?? ?? ?? 89 35 ?? ?? ?? ?? 89 3D");
+                let addr = proc.scan_signature(&SIG, core_address, proc.module_size(&core_name)?)?.0 as i64 + 3;
+                let ptr = addr + 0x4 + proc.read::<i32>(Address(addr as u64))? as i64;
         
-            let wram = proc.read::<u64>(Address(ptr as u64)).ok()?;
-            Some(Address(wram))
-        } else {
-            const SIG: Signature<8> = Signature::new("A1 ?? ?? ?? ?? 23 CB 8B");
-            let ptr = SIG.scan_process_range(proc, core_address, proc.get_module_size(core).ok()?)?.0 + 1;
-            let ptr = proc.read::<u32>(Address(ptr)).ok()? as u64;
+                let wram = proc.read::<u64>(Address(ptr as u64))?;
+                Some((Address(wram), Address(ptr as u64)))
+            } else {
+                const SIG: Signature<8> = Signature::new("A1 ?? ?? ?? ?? 23 CB 8B");
+                let ptr = proc.scan_signature(&SIG, core_address, proc.module_size(&core_name)?)?.0 + 1;
+                let ptr = proc.read::<u32>(Address(ptr))? as u64;
 
-            let ptr = proc.read::<u32>(Address(ptr)).ok()? as u64;
-            Some(Address(ptr))        }
-    } else if core == SUPPORTED_CORES[3] {
-        // PCSX ReARMed
-        if is_64_bit {
-            const SIG: Signature<9> = Signature::new("48 8B 35 ?? ?? ?? ?? 81 E2");
-            let addr = SIG.scan_process_range(proc, core_address, proc.get_module_size(core).ok()?)?.0 as i64 + 3;
-            let ptr = addr + 0x4 + proc.read::<i32>(Address(addr as u64)).ok()? as i64;
-            let ptr = proc.read::<u64>(Address(ptr as u64)).ok()?;
+                let wram = proc.read::<u32>(Address(ptr))? as u64;
+                Some((Address(wram), Address(ptr)))        }
+        } else if core == RetroarchCore::PcsxRearmed {
+            // PCSX ReARMed
+            if is_64_bit {
+                const SIG: Signature<9> = Signature::new("48 8B 35 ?? ?? ?? ?? 81 E2");
+                let addr = proc.scan_signature(&SIG, core_address, proc.module_size(&core_name)?)?.0 as i64 + 3;
+                let ptr = addr + 0x4 + proc.read::<i32>(Address(addr as u64))? as i64;
+                let ptr = proc.read::<u64>(Address(ptr as u64))?;
             
-            let wram = proc.read::<u64>(Address(ptr)).ok()?;
-            Some(Address(wram))
+                let wram = proc.read::<u64>(Address(ptr))?;
+                Some((Address(wram), Address(ptr)))
+            } else {
+                const SIG: Signature<9> = Signature::new("FF FF 1F 00 89 ?? ?? ?? A1");
+                let ptr = proc.scan_signature(&SIG, core_address, proc.module_size(&core_name)?)?.0 + 9;
+                let ptr = Address(proc.read::<u32>(Address(ptr))? as u64);
+
+                let wram = Address(proc.read::<u32>(ptr)? as u64);
+                Some((wram, ptr))
+            }        
         } else {
-            const SIG: Signature<9> = Signature::new("FF FF 1F 00 89 ?? ?? ?? A1");
-            let ptr = SIG.scan_process_range(proc, core_address, proc.get_module_size(core).ok()?)?.0 + 9;
-            let ptr = Address(proc.read::<u32>(Address(ptr)).ok()? as u64);
+            None
+        }?;
 
-            let ptr = Address(proc.read::<u32>(ptr).ok()? as u64);
-            Some(ptr)
-        }        
-    } else {
-        None
+        // Only PCSX ReARMed keeps the hardware registers in a flat buffer, along with the scratchpad
+        let mut ram = Ram::new(base);
+        self.regions.locate(proc, &mut ram, anchor, is_64_bit, core == RetroarchCore::PcsxRearmed);
+        Some(ram)
     }
-}
 
-pub fn keep_alive(game: &super::ProcessInfo) -> bool {
-    let static_data = STATICDATA.lock();
-    game.emulator_process.read::<u8>(static_data.core_addr).is_ok()
+    fn keep_alive<P: MemorySource>(&mut self, proc: &P, ram: &mut Ram) -> bool {
+        self.regions.update(proc, ram);
+        proc.read::<u8>(self.core_addr).is_some()
+    }
+
+    fn core_loaded<P: MemorySource>(&self, proc: &P) -> bool {
+        self.core_address(proc).is_some()
+    }
+
+    fn core_address<P: MemorySource>(&self, proc: &P) -> Option<Address> {
+        self.cores.apply(&SUPPORTED_CORES, |c| c.1).find_map(|c| c.0.find_module(proc)).map(|m| m.1)
+    }
 }
\ No newline at end of file
@@ -1,21 +1,28 @@
 use asr::Address;
 use asr::signature::Signature;
+use crate::shared::{EmulatorBackend, MemorySource, main_module};
+use super::{Ram, regions};
 
-pub fn xebra(game: &super::ProcessInfo) -> Option<Address> {
-    const NAME: &str = "XEBRA.EXE";
-    const SIG: Signature<15> = Signature::new("E8 ?? ?? ?? ?? E9 ?? ?? ?? ?? 89 C8 C1 F8 10");
-    let proc = &game.emulator_process;
+#[derive(Default)]
+pub struct Xebra;
 
-    let main_module_base = proc.get_module_address(NAME).ok()?;
-    let main_module_size = proc.get_module_size(NAME).ok()?;
+impl EmulatorBackend<super::Ps1> for Xebra {
+    fn find_ram<P: MemorySource>(&mut self, proc: &P) -> Option<Ram> {
+        const SIG: Signature<15> = Signature::new("E8 ?? ?? ?? ?? E9 ?? ?? ?? ?? 89 C8 C1 F8 10");
 
-    let ptr = SIG.scan_process_range(proc, main_module_base, main_module_size)?.0 as i32 + 1;
-    let addr = ptr + 0x4 + proc.read::<i32>(Address(ptr as u64)).ok()?;
-    let addr = proc.read::<i32>(Address(addr as u64 + 0x16A)).ok()?;
-    let addr = proc.read::<i32>(Address(addr as u64)).ok()?;
-    Some(Address(addr as u64))
-}
+        let (main_module_name, main_module_base) = main_module::<super::Ps1, _>(proc, super::EmulatorType::Xebra)?;
+        let main_module_size = proc.module_size(&main_module_name)?;
+
+        let ptr = proc.scan_signature(&SIG, main_module_base, main_module_size)?.0 as i32 + 1;
+        let addr = ptr + 0x4 + proc.read::<i32>(Address(ptr as u64))?;
+        let addr = proc.read::<i32>(Address(addr as u64 + 0x16A))?;
+        let addr = proc.read::<i32>(Address(addr as u64))?;
+        let mut ram = Ram::new(Address(addr as u64));
+        regions::locate_near_ram(proc, &mut ram);
+        Some(ram)
+    }
 
-pub fn keep_alive() -> bool {
-    true
-}
\ No newline at end of file
+    fn keep_alive<P: MemorySource>(&mut self, _proc: &P, _ram: &mut Ram) -> bool {
+        true
+    }
+}
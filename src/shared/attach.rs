@@ -0,0 +1,122 @@
+use asr::{Process, ProcessId};
+use super::{Console, NamePattern, Selection};
+
+/// Maximum number of running emulators listed by [`Emulator::candidates`](super::Emulator::candidates).
+pub const MAX_CANDIDATES: usize = 16;
+
+/// A running emulator that can be attached to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Candidate<E> {
+    /// Name of the console emulated by the emulator, such as `"PS1"`.
+    pub console: &'static str,
+    /// The emulator, determining the backend used to find the emulated RAM.
+    pub emulator_type: E,
+    /// ID of the emulator process.
+    pub pid: ProcessId,
+}
+
+/// Decides which process gets attached to when several supported emulators are running.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AttachPolicy {
+    /// Attaches to the first emulator found, in the order set by the [`Selection`].
+    #[default]
+    FirstFound,
+    /// Only attaches to the process with the provided ID.
+    Pid(ProcessId),
+    /// Attaches to the first emulator found, then keeps coming back to the same process
+    /// for as long as it's running, even after being detached from it.
+    Sticky,
+}
+
+/// Maximum length of a process name on Linux, where longer names (`comm`) get truncated.
+/// This includes both native emulators and the ones running under Wine or Proton.
+const MAX_COMM_LEN: usize = 15;
+
+/// Attaches to a process whose name is matched by the provided pattern.
+///
+/// If no process can be found by its full name, the names longer than [`MAX_COMM_LEN`] are
+/// tried again after being truncated. As a truncated name could belong to a different program,
+/// such processes are only accepted if their modules include one matching the pattern.
+pub(crate) fn attach_by_pattern(pattern: &NamePattern) -> Option<Process> {
+    if let Some(process) = pattern.names().find_map(|name| Process::attach(&name)) {
+        return Some(process)
+    }
+
+    let mut last_tried = None;
+
+    pattern.names().filter(|name| name.len() > MAX_COMM_LEN).find_map(|name| {
+        let truncated = name.truncated(MAX_COMM_LEN);
+
+        // Consecutive names often share their first characters, and would attach to the same process again
+        if last_tried.as_deref() == Some(&*truncated) {
+            return None
+        }
+        last_tried = Some(truncated);
+
+        let process = Process::attach(&truncated)?;
+        pattern.find_module(&process).map(|_| process)
+    })
+}
+
+/// Lists the running processes of the emulators allowed by the provided selection, in the order they should be tried.
+///
+/// Processes are looked for by their full names first, then by their names truncated to [`MAX_COMM_LEN`],
+/// in which case they are only listed if their modules confirm they are the expected emulator.
+pub(crate) fn list_candidates<C: Console>(selection: &Selection<C::EmulatorType>) -> [Option<Candidate<C::EmulatorType>>; MAX_CANDIDATES] {
+    let mut candidates = [None; MAX_CANDIDATES];
+
+    let push = |candidates: &mut [Option<Candidate<C::EmulatorType>>], emulator_type, pid| {
+        if candidates.iter().flatten().any(|candidate| candidate.pid == pid) {
+            return
+        }
+
+        if let Some(slot) = candidates.iter_mut().find(|slot| slot.is_none()) {
+            *slot = Some(Candidate { console: C::NAME, emulator_type, pid })
+        }
+    };
+
+    for &(pattern, emulator_type) in selection.apply(C::PROCESS_NAMES, |p| p.1) {
+        for name in pattern.names() {
+            for pid in Process::list_by_name(&name).into_iter().flatten() {
+                push(&mut candidates, emulator_type, pid)
+            }
+        }
+    }
+
+    for &(pattern, emulator_type) in selection.apply(C::PROCESS_NAMES, |p| p.1) {
+        let mut last_tried = None;
+
+        for name in pattern.names().filter(|name| name.len() > MAX_COMM_LEN) {
+            let truncated = name.truncated(MAX_COMM_LEN);
+
+            if last_tried.as_deref() == Some(&*truncated) {
+                continue
+            }
+            last_tried = Some(truncated);
+
+            for pid in Process::list_by_name(&truncated).into_iter().flatten() {
+                if candidates.iter().flatten().any(|candidate| candidate.pid == pid) {
+                    continue
+                }
+
+                if Process::attach_by_pid(pid).is_some_and(|process| pattern.find_module(&process).is_some()) {
+                    push(&mut candidates, emulator_type, pid)
+                }
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Attaches to the process with the provided ID, identifying the emulator by its modules.
+pub(crate) fn attach_by_pid<C: Console>(pid: ProcessId, selection: &Selection<C::EmulatorType>) -> Option<(Process, C::EmulatorType)> {
+    let process = Process::attach_by_pid(pid)?;
+
+    let emulator_type = selection
+        .apply(C::PROCESS_NAMES, |p| p.1)
+        .find(|(pattern, _)| pattern.find_module(&process).is_some())?
+        .1;
+
+    Some((process, emulator_type))
+}
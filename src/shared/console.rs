@@ -0,0 +1,659 @@
+use asr::{Address, Process, ProcessId};
+use super::{MemorySource, MemoryDomain, MAX_MEMORY_DOMAINS, RescanPolicy, NamePattern, Name, Selection, rescan::Backoff, snapshot::Snapshot, Watch, watch::WatchSet};
+use super::attach::{Candidate, attach_by_pattern, attach_by_pid, list_candidates};
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+use super::AttachPolicy;
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+use asr::sync::Mutex;
+use bytemuck::{CheckedBitPattern, Pod};
+use crate::Error;
+
+/// Describes an emulated console: the emulators supporting it and how its memory map
+/// translates to the host addresses found by those emulators.
+pub trait Console: Sized {
+    /// Name of the console, as reported in the [`Candidate`]s.
+    const NAME: &'static str;
+
+    /// Enum listing the emulators supported for this console.
+    type EmulatorType: Copy + PartialEq + 'static;
+    /// Emulator-specific data, such as pointers cached while looking for the emulated RAM.
+    type Backend: EmulatorBackend<Self>;
+    /// Host addresses of the console's memory, as found by the backend.
+    type Ram: Copy + PartialEq;
+
+    /// Process names of the supported emulators, in the order they are tried when attaching.
+    const PROCESS_NAMES: &'static [(NamePattern, Self::EmulatorType)];
+
+    /// Creates the backend needed in order to hook to the specified emulator.
+    fn backend(emulator_type: Self::EmulatorType) -> Self::Backend;
+
+    /// Converts an address of the original system into the host address it corresponds to.
+    ///
+    /// Returns `Err(Error::OutOfRange)` if a value of `size` bytes at the provided address
+    /// doesn't entirely fit in the console's memory map.
+    fn translate(ram: &Self::Ram, address: u32, size: usize) -> Result<Address, Error>;
+
+    /// Returns the `index`-th memory domain of the console, or `None` past the last one.
+    ///
+    /// At most [`MAX_MEMORY_DOMAINS`](super::MAX_MEMORY_DOMAINS) domains can be exposed.
+    fn memory_domain(ram: &Self::Ram, index: usize) -> Option<MemoryDomain>;
+}
+
+/// Describes how a specific emulator stores the memory of the console it emulates.
+pub trait EmulatorBackend<C: Console> {
+    /// Looks for the emulated RAM inside the emulator process.
+    fn find_ram<P: MemorySource>(&mut self, process: &P) -> Option<C::Ram>;
+
+    /// Checks whether the emulated RAM found by [`EmulatorBackend::find_ram`] is still valid,
+    /// updating its address if the emulator moved it.
+    ///
+    /// Returning false makes the emulated RAM to be looked for again.
+    fn keep_alive<P: MemorySource>(&mut self, process: &P, ram: &mut C::Ram) -> bool;
+
+    /// Checks whether the emulator has loaded a core this backend knows how to hook to.
+    ///
+    /// This is only meaningful for multi-system emulators, such as Retroarch,
+    /// and it's used to tell a missing core apart from a missing RAM signature.
+    fn core_loaded<P: MemorySource>(&self, _process: &P) -> bool {
+        true
+    }
+
+    /// Returns the address of the core currently loaded by a multi-system emulator.
+    ///
+    /// A change in this address resets the backoff applied to the RAM lookups.
+    fn core_address<P: MemorySource>(&self, _process: &P) -> Option<Address> {
+        None
+    }
+}
+
+/// Reports how far the hooking process went during the last update.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AttachStatus<E> {
+    /// No supported emulator is running, or the attached one has been closed.
+    NotRunning,
+    /// The emulator is running, but none of its loaded cores is supported.
+    /// A new scan will be performed on a later update, according to the [`RescanPolicy`].
+    UnsupportedCore(E),
+    /// The emulator is running, but its emulated RAM could not be found, or it's not valid anymore.
+    /// A new scan will be performed on a later update, according to the [`RescanPolicy`].
+    RamNotFound(E),
+    /// The emulated RAM has been found and can be read from.
+    Hooked(E),
+}
+
+impl<E: Copy> AttachStatus<E> {
+    /// Returns the emulator that has been detected, if any.
+    pub fn emulator(&self) -> Option<E> {
+        match self {
+            Self::NotRunning => None,
+            Self::UnsupportedCore(e) | Self::RamNotFound(e) | Self::Hooked(e) => Some(*e),
+        }
+    }
+
+    /// Returns true if the emulated RAM can be read from.
+    pub fn is_hooked(&self) -> bool {
+        matches!(self, Self::Hooked(_))
+    }
+
+    /// Returns true if the emulated RAM is going to be looked for again.
+    pub fn rescan_pending(&self) -> bool {
+        matches!(self, Self::UnsupportedCore(_) | Self::RamNotFound(_))
+    }
+}
+
+/// A handle to a running emulator for the console `C`.
+///
+/// Each handle owns the attached process, the addresses of the emulated RAM and
+/// any pointer cached by the emulator-specific code, so multiple emulators can be
+/// tracked at the same time without sharing any state.
+///
+/// The process is accessed through a [`MemorySource`], which is an [`asr::Process`]
+/// unless the handle is built with [`Emulator::new`].
+pub struct Emulator<C: Console, P: MemorySource = Process> {
+    emulator_type: C::EmulatorType,
+    process: P,
+    backend: C::Backend,
+    ram: Option<C::Ram>,
+    status: AttachStatus<C::EmulatorType>,
+    backoff: Backoff,
+    pid: Option<ProcessId>,
+    snapshot: Option<Snapshot>,
+    watches: WatchSet<Self>,
+}
+
+impl<C: Console> Emulator<C> {
+    /// Attaches to the first supported emulator found running.
+    ///
+    /// The emulated RAM is not looked for until [`Emulator::update`] gets called.
+    pub fn attach() -> Option<Self> {
+        Self::attach_with(&Selection::any())
+    }
+
+    /// Attaches to the first emulator found running among the ones allowed by the provided selection,
+    /// trying them in the order it defines.
+    pub fn attach_with(selection: &Selection<C::EmulatorType>) -> Option<Self> {
+        let (process, emulator_type) = selection
+            .apply(C::PROCESS_NAMES, |p| p.1)
+            .find_map(|(pattern, emulator_type)| Some((attach_by_pattern(pattern)?, *emulator_type)))?;
+
+        Some(Self::new(process, emulator_type))
+    }
+
+    /// Lists every running emulator allowed by the provided selection, in the order they would be attached to.
+    ///
+    /// At most [`MAX_CANDIDATES`](super::MAX_CANDIDATES) emulators are listed.
+    pub fn candidates(selection: &Selection<C::EmulatorType>) -> impl Iterator<Item = Candidate<C::EmulatorType>> {
+        list_candidates::<C>(selection).into_iter().flatten()
+    }
+
+    /// Attaches to an emulator listed by [`Emulator::candidates`].
+    pub fn attach_to(candidate: &Candidate<C::EmulatorType>) -> Option<Self> {
+        let process = Process::attach_by_pid(candidate.pid)?;
+        Some(Self { pid: Some(candidate.pid), ..Self::new(process, candidate.emulator_type) })
+    }
+
+    /// Attaches to the process with the provided ID, as long as it's one of the supported emulators.
+    pub fn attach_by_pid(pid: ProcessId) -> Option<Self> {
+        let (process, emulator_type) = attach_by_pid::<C>(pid, &Selection::any())?;
+        Some(Self { pid: Some(pid), ..Self::new(process, emulator_type) })
+    }
+}
+
+impl<C: Console, P: MemorySource> Emulator<C, P> {
+    /// Creates a handle for the specified emulator, running inside the provided memory source.
+    pub fn new(process: P, emulator_type: C::EmulatorType) -> Self {
+        Self {
+            emulator_type,
+            process,
+            backend: C::backend(emulator_type),
+            ram: None,
+            status: AttachStatus::RamNotFound(emulator_type),
+            backoff: Backoff::new(RescanPolicy::DEFAULT),
+            pid: None,
+            snapshot: None,
+            watches: WatchSet::new(),
+        }
+    }
+
+    /// Returns the ID of the emulator process, if it's known.
+    ///
+    /// This is only the case for emulators attached to by their process ID or through [`Emulator::candidates`].
+    pub fn pid(&self) -> Option<ProcessId> {
+        self.pid
+    }
+
+    /// Changes the emulator-specific settings. If `f` returns true, the emulated RAM
+    /// is looked for again on the next update.
+    #[cfg(any(feature = "ps1", feature = "genesis"))]
+    pub(crate) fn configure_backend(&mut self, f: impl FnOnce(&mut C::Backend) -> bool) {
+        if f(&mut self.backend) {
+            self.ram = None;
+            self.backoff.reset();
+        }
+    }
+
+    /// Sets how often the emulated RAM is looked for while it can't be found.
+    pub fn set_rescan_policy(&mut self, policy: RescanPolicy) {
+        self.backoff.set_policy(policy)
+    }
+
+    /// Returns the emulator this handle is attached to.
+    pub fn emulator_type(&self) -> C::EmulatorType {
+        self.emulator_type
+    }
+
+    /// Checks whether the emulator process is still open.
+    pub fn is_open(&self) -> bool {
+        self.process.is_open()
+    }
+
+    /// Looks for the emulated RAM if it hasn't been found yet, and checks whether
+    /// the previously found addresses are still valid. If a snapshot is enabled, it's refreshed as well,
+    /// followed by the watched values.
+    ///
+    /// Returns true if the emulated RAM can be read from, false otherwise.
+    /// A more detailed result is available through [`Emulator::status`].
+    pub fn update(&mut self) -> bool {
+        let previous_ram = self.ram;
+        self.status = self.hook();
+
+        if !self.status.is_hooked() || self.ram != previous_ram {
+            self.watches.reset();
+        }
+
+        if self.status.is_hooked() {
+            self.refresh_snapshot();
+            self.refresh_watches();
+        } else {
+            self.invalidate_snapshot();
+        }
+
+        self.status.is_hooked()
+    }
+
+    /// Serves the reads from a local copy of the provided guest ranges, taken on every [`Emulator::update`],
+    /// replacing any previous snapshot.
+    ///
+    /// Each range is an address, in any of the formats accepted by the console's reads, along with its length in bytes.
+    /// Ranges are copied one after the other into `buf`: the ones that don't fit in it, or beyond
+    /// [`MAX_SNAPSHOT_RANGES`](super::MAX_SNAPSHOT_RANGES), keep being read from the emulator,
+    /// along with any read not entirely contained in a single range.
+    ///
+    /// This turns a read per value into a read per range, which pays off for auto splitters
+    /// reading many values on each tick, at the cost of values being as old as the last update.
+    pub fn enable_snapshot(&mut self, buf: &'static mut [u8], ranges: &[(u32, usize)]) {
+        self.snapshot = Some(Snapshot::new(buf, ranges))
+    }
+
+    /// Goes back to reading every value from the emulator, returning the buffer used by the snapshot.
+    pub fn disable_snapshot(&mut self) -> Option<&'static mut [u8]> {
+        self.snapshot.take().map(Snapshot::into_buffer)
+    }
+
+    /// Copies the guest ranges into the snapshot again, outside of [`Emulator::update`].
+    ///
+    /// Returns false if no snapshot is enabled, or if any range could not be read,
+    /// in which case values are read from the emulator until the next successful refresh.
+    pub fn refresh_snapshot(&mut self) -> bool {
+        let Some(snapshot) = &mut self.snapshot else { return false };
+        let Some(ram) = &self.ram else {
+            snapshot.invalidate();
+            return false
+        };
+
+        snapshot.refresh::<C, P>(&self.process, ram)
+    }
+
+    /// Discards the content of the snapshot, so values are read from the emulator until the next refresh.
+    ///
+    /// This is useful after the game is known to have changed some value since the last update.
+    pub fn invalidate_snapshot(&mut self) {
+        if let Some(snapshot) = &mut self.snapshot {
+            snapshot.invalidate()
+        }
+    }
+
+    /// Stops watching the provided value.
+    ///
+    /// The handle, along with its copies, stops returning values, even once another value takes its place.
+    pub fn unwatch<T: 'static>(&mut self, watch: Watch<C, T>) {
+        self.watches.remove(watch)
+    }
+
+    /// Stops watching every value.
+    pub fn clear_watches(&mut self) {
+        self.watches.clear()
+    }
+
+    #[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+    pub(crate) fn watches(&self) -> &WatchSet<Self> {
+        &self.watches
+    }
+
+    #[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+    pub(crate) fn watches_mut(&mut self) -> &mut WatchSet<Self> {
+        &mut self.watches
+    }
+
+    /// Reads every watched value again, outside of [`Emulator::update`].
+    fn refresh_watches(&mut self) {
+        // The values are read through the emulator itself, so the set is moved out of it in the meantime
+        let mut watches = core::mem::replace(&mut self.watches, WatchSet::new());
+        watches.refresh(self);
+        self.watches = watches;
+    }
+
+    /// Returns the result of the last [`Emulator::update`].
+    pub fn status(&self) -> AttachStatus<C::EmulatorType> {
+        self.status
+    }
+
+    fn hook(&mut self) -> AttachStatus<C::EmulatorType> {
+        if !self.process.is_open() {
+            self.ram = None;
+            return AttachStatus::NotRunning
+        }
+
+        if self.ram.is_none() {
+            // Looking up the modules goes through every variant of their names, so it's only done
+            // once a lookup is due
+            if !self.backoff.should_scan() {
+                return self.status
+            }
+
+            self.backoff.track_modules([
+                main_module::<C, P>(&self.process, self.emulator_type).map(|m| m.1),
+                self.backend.core_address(&self.process),
+            ]);
+
+            self.ram = self.backend.find_ram(&self.process);
+        }
+
+        let Some(ram) = &mut self.ram else {
+            self.backoff.failed();
+
+            return if self.backend.core_loaded(&self.process) {
+                AttachStatus::RamNotFound(self.emulator_type)
+            } else {
+                AttachStatus::UnsupportedCore(self.emulator_type)
+            }
+        };
+
+        if !self.backend.keep_alive(&self.process, ram) {
+            self.ram = None;
+            self.backoff.reset();
+            return AttachStatus::RamNotFound(self.emulator_type)
+        }
+
+        AttachStatus::Hooked(self.emulator_type)
+    }
+
+    /// Reads any value from the emulated memory, without performing any endianness conversion.
+    pub fn read_raw<T: CheckedBitPattern>(&self, address: u32) -> Result<T, Error> {
+        let size = core::mem::size_of::<T>();
+        let Some(ram) = &self.ram else { return Err(Error::RamNotFound) };
+        let host = C::translate(ram, address, size)?;
+
+        if let Some(value) = self.snapshot.as_ref().and_then(|snapshot| snapshot.read(host, address)) {
+            return value
+        }
+
+        self.process.read(host)
+            .ok_or(Error::ReadFailed { address, size })
+    }
+
+    /// Fills a slice with values read from the emulated memory, without performing any endianness conversion.
+    ///
+    /// The whole slice has to fit inside a single region of the console's memory map,
+    /// otherwise `Err(Error::OutOfRange)` is returned and nothing is read.
+    pub fn read_raw_into_slice<T: Pod>(&self, address: u32, slice: &mut [T]) -> Result<(), Error> {
+        let buf: &mut [u8] = bytemuck::cast_slice_mut(slice);
+        let size = buf.len();
+        let Some(ram) = &self.ram else { return Err(Error::RamNotFound) };
+        let host = C::translate(ram, address, size)?;
+
+        if self.snapshot.as_ref().is_some_and(|snapshot| snapshot.read_into_buf(host, buf)) {
+            return Ok(())
+        }
+
+        self.process.read_into_buf(host, buf)
+            .ok_or(Error::ReadFailed { address, size })
+    }
+
+    /// Follows a path of guest pointers, in the same way as a `DeepPointer`, and returns the address it leads to.
+    ///
+    /// Each offset but the last one is added to the current address, then the pointer stored there
+    /// is read with `read_pointer`. The last offset is added to the final pointer.
+    #[cfg(any(feature = "ps1", feature = "wii"))]
+    pub(crate) fn resolve_pointer_path_with(&self, base: u32, offsets: &[u32], read_pointer: impl Fn(&Self, u32) -> Result<u32, Error>) -> Result<u32, Error> {
+        let Some((&last, hops)) = offsets.split_last() else { return Ok(base) };
+        let mut address = base;
+
+        for &offset in hops {
+            let location = address.wrapping_add(offset);
+            address = read_pointer(self, location)?;
+
+            if address == 0 {
+                return Err(Error::NullPointer { address: location })
+            }
+        }
+
+        Ok(address.wrapping_add(last))
+    }
+
+    /// Returns the host addresses of the emulated RAM, if they have been found.
+    pub fn ram(&self) -> Option<&C::Ram> {
+        self.ram.as_ref()
+    }
+
+    /// Lists the regions of the emulated system's memory, along with their host addresses.
+    ///
+    /// Nothing is listed until the emulated RAM has been found.
+    pub fn memory_domains(&self) -> impl Iterator<Item = MemoryDomain> + '_ {
+        self.ram.iter().flat_map(|ram| (0..MAX_MEMORY_DOMAINS).map_while(move |index| C::memory_domain(ram, index)))
+    }
+
+    /// Returns the emulator-specific data, such as the core hooked to by multi-system emulators.
+    #[cfg(any(feature = "ps1", feature = "genesis"))]
+    pub(crate) fn backend(&self) -> &C::Backend {
+        &self.backend
+    }
+}
+
+/// Looks for the main module of the specified emulator, returning the name that matched along with its address.
+pub(crate) fn main_module<C: Console, P: MemorySource>(process: &P, emulator_type: C::EmulatorType) -> Option<(Name, Address)> {
+    C::PROCESS_NAMES.iter()
+        .filter(|p| p.1 == emulator_type)
+        .find_map(|p| p.0.find_module(process))
+}
+
+/// The global emulator instance of a console, along with the settings applied to it.
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) struct Global<C: Console> {
+    emulator: Option<Emulator<C>>,
+    rescan_policy: RescanPolicy,
+    selection: Selection<C::EmulatorType>,
+    attach_policy: AttachPolicy,
+    last_pid: Option<ProcessId>,
+    /// Spaces out the attempts to attach while no emulator is running, as each of them looks up every process name.
+    attach_backoff: Backoff,
+    /// The snapshot kept while no emulator is attached.
+    snapshot: Option<Snapshot>,
+    /// The watched values kept while no emulator is attached.
+    watches: WatchSet<Emulator<C>>,
+}
+
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+impl<C: Console> Global<C> {
+    pub const fn new() -> Self {
+        Self {
+            emulator: None,
+            rescan_policy: RescanPolicy::DEFAULT,
+            selection: Selection::any(),
+            attach_policy: AttachPolicy::FirstFound,
+            last_pid: None,
+            attach_backoff: Backoff::new(RescanPolicy::DEFAULT),
+            snapshot: None,
+            watches: WatchSet::new(),
+        }
+    }
+
+    /// Detaches from the current emulator, keeping its snapshot and watched values for the next one.
+    fn detach(&mut self) {
+        let Some(mut emulator) = self.emulator.take() else { return };
+
+        if let Some(mut snapshot) = emulator.snapshot.take() {
+            snapshot.invalidate();
+            self.snapshot = Some(snapshot)
+        }
+
+        emulator.watches.reset();
+        self.watches = emulator.watches;
+    }
+
+    /// Returns the watched values of the attached emulator, or the ones kept while no emulator is attached.
+    fn watches(&mut self) -> &mut WatchSet<Emulator<C>> {
+        match &mut self.emulator {
+            Some(emulator) => &mut emulator.watches,
+            None => &mut self.watches,
+        }
+    }
+
+    /// Returns the snapshot of the attached emulator, or the one kept while no emulator is attached.
+    fn snapshot(&mut self) -> &mut Option<Snapshot> {
+        match &mut self.emulator {
+            Some(emulator) => &mut emulator.snapshot,
+            None => &mut self.snapshot,
+        }
+    }
+
+    /// Attaches to an emulator according to the current selection and policy.
+    fn attach(&mut self) -> Option<Emulator<C>> {
+        let allowed = |emulator: &Emulator<C>| self.selection.allows(emulator.emulator_type());
+
+        match self.attach_policy {
+            AttachPolicy::FirstFound => Emulator::attach_with(&self.selection),
+            AttachPolicy::Pid(pid) => Emulator::<C>::attach_by_pid(pid).filter(allowed),
+            AttachPolicy::Sticky => self.last_pid
+                .and_then(Emulator::<C>::attach_by_pid)
+                .filter(allowed)
+                .or_else(|| Emulator::<C>::candidates(&self.selection).find_map(|candidate| Emulator::attach_to(&candidate))),
+        }
+    }
+}
+
+/// Hooks the global emulator instance of a console, attaching to a new emulator if needed.
+///
+/// `on_attach` is called on newly attached emulators, in order to apply any console-specific setting.
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) fn update_global<C: Console>(state: &Mutex<Global<C>>, on_attach: impl FnOnce(&mut Emulator<C>)) -> bool {
+    let mut state = state.lock();
+    let state = &mut *state;
+    let rescan_policy = state.rescan_policy;
+
+    if state.emulator.is_none() && state.attach_backoff.should_scan() {
+        state.emulator = state.attach().map(|mut emulator| {
+            emulator.set_rescan_policy(rescan_policy);
+            on_attach(&mut emulator);
+            emulator.snapshot = state.snapshot.take();
+            emulator.watches = core::mem::replace(&mut state.watches, WatchSet::new());
+            emulator
+        });
+
+        if let Some(pid) = state.emulator.as_ref().and_then(|emulator| emulator.pid()) {
+            state.last_pid = Some(pid)
+        }
+
+        match state.emulator {
+            Some(_) => state.attach_backoff.reset(),
+            None => state.attach_backoff.failed(),
+        }
+    }
+
+    let Some(emulator) = &mut state.emulator else {
+        return false
+    };
+
+    if !emulator.is_open() {
+        state.detach();
+        return false
+    }
+
+    emulator.update()
+}
+
+/// Returns the status of the global emulator instance of a console.
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) fn status_global<C: Console>(state: &Mutex<Global<C>>) -> AttachStatus<C::EmulatorType> {
+    state.lock().emulator.as_ref().map_or(AttachStatus::NotRunning, |emulator| emulator.status())
+}
+
+/// Sets the rescan policy of the global emulator instance of a console, including the ones attached later on.
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) fn set_rescan_policy_global<C: Console>(state: &Mutex<Global<C>>, policy: RescanPolicy) {
+    let mut state = state.lock();
+    state.rescan_policy = policy;
+    state.attach_backoff.set_policy(policy);
+
+    if let Some(emulator) = &mut state.emulator {
+        emulator.set_rescan_policy(policy)
+    }
+}
+
+/// Sets which emulators the global instance of a console can attach to, and in which order they are tried.
+///
+/// If the emulator currently attached is not allowed anymore, it gets detached.
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) fn set_selection_global<C: Console>(state: &Mutex<Global<C>>, selection: Selection<C::EmulatorType>) {
+    let mut state = state.lock();
+    state.selection = selection;
+    state.attach_backoff.reset();
+
+    if state.emulator.as_ref().is_some_and(|emulator| !selection.allows(emulator.emulator_type())) {
+        state.detach()
+    }
+}
+
+/// Sets which process the global emulator instance of a console attaches to, when several emulators are running.
+///
+/// If the emulator currently attached is not the one required by an [`AttachPolicy::Pid`], it gets detached.
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) fn set_attach_policy_global<C: Console>(state: &Mutex<Global<C>>, policy: AttachPolicy) {
+    let mut state = state.lock();
+    state.attach_policy = policy;
+    state.attach_backoff.reset();
+
+    if let AttachPolicy::Pid(pid) = policy {
+        if state.emulator.as_ref().is_some_and(|emulator| emulator.pid() != Some(pid)) {
+            state.detach()
+        }
+    }
+}
+
+/// Runs a change on the global emulator instance of a console, if it's attached.
+#[cfg(any(feature = "ps1", feature = "genesis"))]
+pub(crate) fn configure_global<C: Console>(state: &Mutex<Global<C>>, f: impl FnOnce(&mut Emulator<C>)) {
+    if let Some(emulator) = &mut state.lock().emulator {
+        f(emulator)
+    }
+}
+
+/// Runs a read on the global emulator instance of a console.
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) fn read_global<C: Console, T>(state: &Mutex<Global<C>>, f: impl FnOnce(&Emulator<C>) -> Result<T, Error>) -> Result<T, Error> {
+    let state = state.lock();
+    let Some(emulator) = &state.emulator else { return Err(Error::NotAttached) };
+    f(emulator)
+}
+
+/// Enables the snapshot of the global emulator instance of a console, keeping it across emulators being attached and detached.
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) fn enable_snapshot_global<C: Console>(state: &Mutex<Global<C>>, buf: &'static mut [u8], ranges: &[(u32, usize)]) {
+    *state.lock().snapshot() = Some(Snapshot::new(buf, ranges))
+}
+
+/// Disables the snapshot of the global emulator instance of a console, returning its buffer.
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) fn disable_snapshot_global<C: Console>(state: &Mutex<Global<C>>) -> Option<&'static mut [u8]> {
+    state.lock().snapshot().take().map(Snapshot::into_buffer)
+}
+
+/// Refreshes the snapshot of the global emulator instance of a console.
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) fn refresh_snapshot_global<C: Console>(state: &Mutex<Global<C>>) -> bool {
+    state.lock().emulator.as_mut().is_some_and(Emulator::refresh_snapshot)
+}
+
+/// Discards the content of the snapshot of the global emulator instance of a console.
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) fn invalidate_snapshot_global<C: Console>(state: &Mutex<Global<C>>) {
+    if let Some(snapshot) = state.lock().snapshot() {
+        snapshot.invalidate()
+    }
+}
+
+/// Queries the global emulator instance of a console, returning `None` if it's not attached.
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) fn query_global<C: Console, T>(state: &Mutex<Global<C>>, f: impl FnOnce(&Emulator<C>) -> T) -> Option<T> {
+    state.lock().emulator.as_ref().map(f)
+}
+
+/// Copies the memory domains of the global emulator instance of a console, as they can't be borrowed past the lock.
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) fn memory_domains_global<C: Console>(state: &Mutex<Global<C>>) -> impl Iterator<Item = MemoryDomain> {
+    let mut domains = [None; MAX_MEMORY_DOMAINS];
+
+    query_global(state, |emulator| {
+        for (slot, domain) in domains.iter_mut().zip(emulator.memory_domains()) {
+            *slot = Some(domain)
+        }
+    });
+
+    domains.into_iter().flatten()
+}
+
+/// Changes the values watched by the global emulator instance of a console, keeping them across emulators being attached and detached.
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) fn watches_global<C: Console, R>(state: &Mutex<Global<C>>, f: impl FnOnce(&mut WatchSet<Emulator<C>>) -> R) -> R {
+    f(state.lock().watches())
+}
@@ -0,0 +1,36 @@
+use asr::{Address, primitives::dynamic_endian::Endian};
+
+/// Maximum number of memory domains a console can expose.
+pub const MAX_MEMORY_DOMAINS: usize = 8;
+
+/// A region of the emulated system's memory, along with where the emulator stores it.
+///
+/// This allows using asr's own APIs, such as pointer paths or signature scans, directly on the emulated memory.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MemoryDomain {
+    /// Name of the region, such as `"RAM"` or `"MEM1"`.
+    pub name: &'static str,
+    /// First address of the region, as used on the original system.
+    pub guest_address: u32,
+    /// Size of the region, in bytes.
+    pub size: u32,
+    /// Address of the region inside the emulator process.
+    pub host_address: Address,
+    /// Byte order the region is stored in by the emulator, which isn't necessarily the one of the original system.
+    pub endian: Endian,
+}
+
+impl MemoryDomain {
+    /// Checks whether the provided guest address belongs to this region.
+    pub fn contains(&self, address: u32) -> bool {
+        address.wrapping_sub(self.guest_address) < self.size
+    }
+
+    /// Converts a guest address belonging to this region into the host address it's stored at.
+    ///
+    /// No byte order conversion is applied, so addresses inside emulators storing big endian systems
+    /// as little endian words don't point to the same byte as on the original system.
+    pub fn to_host(&self, address: u32) -> Option<Address> {
+        self.contains(address).then(|| Address(self.host_address.0 + (address - self.guest_address) as u64))
+    }
+}
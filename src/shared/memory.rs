@@ -0,0 +1,159 @@
+use asr::{Address, MemoryRangeFlags, Process, signature::Signature};
+use bytemuck::CheckedBitPattern;
+
+/// A memory range of the emulator process.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MemoryRegion {
+    pub address: Address,
+    pub size: u64,
+    pub flags: MemoryRangeFlags,
+}
+
+/// Abstraction over the process the emulators are running in.
+///
+/// Every backend locates the emulated RAM exclusively through this trait. [`Process`]
+/// is the implementation used at runtime, while [`InMemoryProcess`] allows the same
+/// code to run against memory images built by hand.
+pub trait MemorySource {
+    /// Checks whether the process is still open.
+    fn is_open(&self) -> bool;
+
+    /// Returns the base address of the module with the given name.
+    fn module_address(&self, name: &str) -> Option<Address>;
+
+    /// Returns the size of the module with the given name.
+    fn module_size(&self, name: &str) -> Option<u64>;
+
+    /// Iterates over all the memory ranges of the process.
+    fn memory_ranges(&self) -> impl Iterator<Item = MemoryRegion> + '_;
+
+    /// Reads a value from the process' memory.
+    fn read<T: CheckedBitPattern>(&self, address: Address) -> Option<T>;
+
+    /// Fills the provided buffer with the bytes found at the given address.
+    fn read_into_buf(&self, address: Address, buf: &mut [u8]) -> Option<()>;
+
+    /// Looks for the signature in the memory range starting at `address`,
+    /// returning the address of its first occurrence.
+    fn scan_signature<const N: usize>(&self, signature: &Signature<N>, address: Address, len: u64) -> Option<Address>;
+}
+
+impl MemorySource for Process {
+    fn is_open(&self) -> bool {
+        Process::is_open(self)
+    }
+
+    fn module_address(&self, name: &str) -> Option<Address> {
+        self.get_module_address(name).ok()
+    }
+
+    fn module_size(&self, name: &str) -> Option<u64> {
+        self.get_module_size(name).ok()
+    }
+
+    fn memory_ranges(&self) -> impl Iterator<Item = MemoryRegion> + '_ {
+        Process::memory_ranges(self).filter_map(|m| Some(MemoryRegion {
+            address: m.address().ok()?,
+            size: m.size().ok()?,
+            flags: m.flags().unwrap_or_default(),
+        }))
+    }
+
+    fn read<T: CheckedBitPattern>(&self, address: Address) -> Option<T> {
+        Process::read(self, address).ok()
+    }
+
+    fn read_into_buf(&self, address: Address, buf: &mut [u8]) -> Option<()> {
+        Process::read_into_buf(self, address, buf).ok()
+    }
+
+    fn scan_signature<const N: usize>(&self, signature: &Signature<N>, address: Address, len: u64) -> Option<Address> {
+        signature.scan_process_range(self, address, len)
+    }
+}
+
+/// A loaded module of an [`InMemoryProcess`].
+#[derive(Copy, Clone, Debug)]
+pub struct Module<'a> {
+    pub name: &'a str,
+    pub address: Address,
+    pub size: u64,
+}
+
+/// A block of memory of an [`InMemoryProcess`], mapped at `address`.
+#[derive(Copy, Clone, Debug)]
+pub struct MemoryBlock<'a> {
+    pub address: Address,
+    pub flags: MemoryRangeFlags,
+    pub data: &'a [u8],
+}
+
+/// A [`MemorySource`] backed by plain byte slices.
+///
+/// This is meant for testing the backends outside of a live runtime: module images and
+/// memory ranges can be laid out by hand so that signatures and pointer chains resolve
+/// exactly like they would inside the real emulator.
+#[derive(Copy, Clone, Debug)]
+pub struct InMemoryProcess<'a> {
+    pub modules: &'a [Module<'a>],
+    pub blocks: &'a [MemoryBlock<'a>],
+}
+
+impl<'a> InMemoryProcess<'a> {
+    /// Returns the bytes mapped from `address` up to the end of the block containing it.
+    fn slice_at(&self, address: Address) -> Option<&'a [u8]> {
+        self.blocks.iter().find_map(|block| {
+            let offset = address.0.checked_sub(block.address.0)?;
+            block.data.get(offset as usize..)
+        }).filter(|data| !data.is_empty())
+    }
+}
+
+impl MemorySource for InMemoryProcess<'_> {
+    fn is_open(&self) -> bool {
+        true
+    }
+
+    fn module_address(&self, name: &str) -> Option<Address> {
+        self.modules.iter().find(|m| m.name == name).map(|m| m.address)
+    }
+
+    fn module_size(&self, name: &str) -> Option<u64> {
+        self.modules.iter().find(|m| m.name == name).map(|m| m.size)
+    }
+
+    fn memory_ranges(&self) -> impl Iterator<Item = MemoryRegion> + '_ {
+        self.blocks.iter().map(|block| MemoryRegion {
+            address: block.address,
+            size: block.data.len() as u64,
+            flags: block.flags,
+        })
+    }
+
+    fn read<T: CheckedBitPattern>(&self, address: Address) -> Option<T> {
+        let data = self.slice_at(address)?.get(..core::mem::size_of::<T>())?;
+        bytemuck::checked::try_pod_read_unaligned(data).ok()
+    }
+
+    fn read_into_buf(&self, address: Address, buf: &mut [u8]) -> Option<()> {
+        buf.copy_from_slice(self.slice_at(address)?.get(..buf.len())?);
+        Some(())
+    }
+
+    fn scan_signature<const N: usize>(&self, signature: &Signature<N>, address: Address, len: u64) -> Option<Address> {
+        let data = self.slice_at(address)?;
+        let data = &data[..data.len().min(len as usize)];
+        scan_slice(signature, data).map(|offset| Address(address.0 + offset as u64))
+    }
+}
+
+/// Returns the offset of the first occurrence of the signature inside `haystack`.
+fn scan_slice<const N: usize>(signature: &Signature<N>, haystack: &[u8]) -> Option<usize> {
+    haystack.windows(N).position(|window| match signature {
+        Signature::Simple(needle) => window == needle,
+        Signature::Complex { needle, mask, .. } => window
+            .iter()
+            .zip(needle.iter().zip(mask))
+            .all(|(byte, (needle, mask))| byte & mask == *needle),
+    })
+}
@@ -1,6 +1,38 @@
-use asr::{Process, Address, signature::Signature};
+use asr::{Address, signature::Signature};
+mod console;
+mod memory;
+mod rescan;
+mod names;
+mod selection;
+mod attach;
+mod ptr;
+mod snapshot;
+mod domain;
+mod watch;
+#[cfg(feature = "shift-jis")]
+mod shift_jis;
+pub use console::{Console, EmulatorBackend, Emulator, AttachStatus};
+pub use memory::{MemorySource, MemoryRegion, InMemoryProcess, Module, MemoryBlock};
+pub use rescan::RescanPolicy;
+pub use names::{NamePattern, Name, Names, MAX_NAME_LEN};
+pub use selection::{Selection, MAX_SELECTION_LEN};
+pub use attach::{Candidate, AttachPolicy, MAX_CANDIDATES};
+pub use ptr::EmuPtr;
+pub use snapshot::{SnapshotBuffer, MAX_SNAPSHOT_RANGES};
+pub use domain::{MemoryDomain, MAX_MEMORY_DOMAINS};
+pub use watch::{Watch, MAX_WATCHES, MAX_WATCH_SIZE};
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) use watch::store;
+#[cfg(feature = "shift-jis")]
+pub use shift_jis::{ShiftJis, Chars};
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) use console::{Global, update_global, status_global, set_rescan_policy_global, set_selection_global, set_attach_policy_global, read_global, enable_snapshot_global, disable_snapshot_global, refresh_snapshot_global, invalidate_snapshot_global, query_global, memory_domains_global, watches_global};
+#[cfg(any(feature = "ps1", feature = "genesis"))]
+pub(crate) use console::{main_module, configure_global};
+#[cfg(feature = "ps1")]
+pub(crate) use rescan::Backoff;
 
-pub fn check_for_64_bit(proc: &Process, main_module_base: Address) -> bool {
+pub fn check_for_64_bit<P: MemorySource>(proc: &P, main_module_base: Address) -> bool {
     const SIG_64: Signature<5> = Signature::new("50 45 00 00 64");
-    SIG_64.scan_process_range(proc, main_module_base, 0x1000).is_some()
-}
\ No newline at end of file
+    proc.scan_signature(&SIG_64, main_module_base, 0x1000).is_some()
+}
@@ -0,0 +1,197 @@
+use asr::Address;
+use core::{fmt, ops::Deref, str};
+use super::MemorySource;
+
+/// Maximum length, in bytes, of the names generated by a [`NamePattern`].
+pub const MAX_NAME_LEN: usize = 64;
+
+/// A pattern describing the possible names of a process or a module.
+///
+/// Alternatives are written in braces and separated by commas, like in shell globs:
+/// `"duckstation-{qt,nogui}{.exe,}"` matches four different names, the empty alternative
+/// standing for no extension at all.
+///
+/// Processes and modules can only be looked up by their exact name, and asr can't list them,
+/// so a pattern stands for a fixed list of names: every combination of its alternatives, tried first
+/// as written, then in lowercase. Other casings and renamed builds can't be found.
+/// [`NamePattern::matches`] compares a name obtained by other means, ignoring its ASCII casing.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NamePattern(&'static str);
+
+impl NamePattern {
+    /// Creates a new pattern. Braces can't be nested.
+    pub const fn new(pattern: &'static str) -> Self {
+        Self(pattern)
+    }
+
+    /// Returns the pattern as it was written.
+    pub const fn as_str(&self) -> &'static str {
+        self.0
+    }
+
+    /// Iterates over every name looked up for this pattern, in the order they are tried.
+    pub fn names(&self) -> Names {
+        Names { pattern: *self, combinations: self.combinations(), index: 0 }
+    }
+
+    /// Checks whether the provided name is matched by this pattern, ignoring ASCII casing.
+    pub fn matches(&self, name: &str) -> bool {
+        (0..self.combinations())
+            .filter_map(|combination| self.expand(combination, Casing::AsWritten))
+            .any(|expanded| expanded.eq_ignore_ascii_case(name))
+    }
+
+    /// Looks for a module matching this pattern inside the provided process.
+    ///
+    /// Returns the name that actually matched, along with the module's address.
+    pub fn find_module<P: MemorySource>(&self, process: &P) -> Option<(Name, Address)> {
+        self.names().find_map(|name| {
+            let address = process.module_address(&name)?;
+            Some((name, address))
+        })
+    }
+
+    fn segments(&self) -> Segments<'static> {
+        Segments(self.0)
+    }
+
+    fn combinations(&self) -> usize {
+        self.segments().map(|segment| segment.alternatives().count()).product()
+    }
+
+    /// Builds one of the names matched by this pattern, picking the alternatives described by `combination`.
+    ///
+    /// Returns `None` if the name is too long, or if changing its casing leaves it untouched,
+    /// as it would then be the same as the name written in the pattern.
+    fn expand(&self, mut combination: usize, casing: Casing) -> Option<Name> {
+        let mut name = Name { buf: [0; MAX_NAME_LEN], len: 0 };
+
+        for segment in self.segments() {
+            let count = segment.alternatives().count();
+            let alternative = segment.alternatives().nth(combination % count).unwrap_or_default();
+            combination /= count;
+
+            name.buf.get_mut(name.len..name.len + alternative.len())?.copy_from_slice(alternative.as_bytes());
+            name.len += alternative.len();
+        }
+
+        let written = &mut name.buf[..name.len];
+        match casing {
+            Casing::AsWritten => {},
+            Casing::Lower if written.iter().any(u8::is_ascii_uppercase) => written.make_ascii_lowercase(),
+            _ => return None,
+        }
+
+        Some(name)
+    }
+}
+
+/// A name generated by a [`NamePattern`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Name {
+    buf: [u8; MAX_NAME_LEN],
+    len: usize,
+}
+
+impl Name {
+    /// Returns this name cut to at most `len` bytes, as done by systems limiting the length of process names.
+    pub fn truncated(&self, len: usize) -> Name {
+        let mut len = len.min(self.len);
+        while !self.is_char_boundary(len) {
+            len -= 1
+        }
+
+        Name { len, ..*self }
+    }
+}
+
+impl Deref for Name {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        // Names are only built by concatenating and changing the ASCII casing of valid UTF-8 strings
+        str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum Casing {
+    AsWritten,
+    Lower,
+}
+
+/// Iterator over the names matched by a [`NamePattern`].
+pub struct Names {
+    pattern: NamePattern,
+    combinations: usize,
+    index: usize,
+}
+
+impl Iterator for Names {
+    type Item = Name;
+
+    fn next(&mut self) -> Option<Name> {
+        loop {
+            let casing = match self.index / self.combinations.max(1) {
+                0 => Casing::AsWritten,
+                1 => Casing::Lower,
+                _ => return None,
+            };
+
+            let combination = self.index % self.combinations.max(1);
+            self.index += 1;
+
+            if let Some(name) = self.pattern.expand(combination, casing) {
+                return Some(name)
+            }
+        }
+    }
+}
+
+/// Either a literal part of a pattern, or a group of alternatives written in braces.
+#[derive(Copy, Clone)]
+struct Segment<'a> {
+    text: &'a str,
+    is_group: bool,
+}
+
+impl<'a> Segment<'a> {
+    fn alternatives(self) -> impl Iterator<Item = &'a str> {
+        self.text.split(move |c| self.is_group && c == ',')
+    }
+}
+
+struct Segments<'a>(&'a str);
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = Segment<'a>;
+
+    fn next(&mut self) -> Option<Segment<'a>> {
+        if self.0.is_empty() {
+            return None
+        }
+
+        if let Some(rest) = self.0.strip_prefix('{') {
+            let end = rest.find('}').unwrap_or(rest.len());
+            self.0 = rest.get(end + 1..).unwrap_or_default();
+            return Some(Segment { text: &rest[..end], is_group: true })
+        }
+
+        let end = self.0.find('{').unwrap_or(self.0.len());
+        let text = &self.0[..end];
+        self.0 = &self.0[end..];
+        Some(Segment { text, is_group: false })
+    }
+}
@@ -0,0 +1,92 @@
+use asr::primitives::dynamic_endian::{Endian, FromEndian};
+use bytemuck::{Pod, Zeroable};
+use core::{fmt, hash::{Hash, Hasher}, marker::PhantomData, mem::size_of};
+
+/// A pointer to a value of type `T`, stored in the memory of the console `C`.
+///
+/// Being a plain 32-bit guest address, it can be used as a field of structs read from the emulated memory.
+/// Each console exposes it with its own alias, such as `ps1::Ptr<T>`, along with the methods to read from it.
+#[repr(transparent)]
+pub struct EmuPtr<C, T> {
+    address: u32,
+    _marker: PhantomData<fn() -> (C, T)>,
+}
+
+impl<C, T> EmuPtr<C, T> {
+    /// Creates a pointer to the provided guest address.
+    pub const fn new(address: u32) -> Self {
+        Self { address, _marker: PhantomData }
+    }
+
+    /// Creates a null pointer.
+    pub const fn null() -> Self {
+        Self::new(0)
+    }
+
+    /// Returns the guest address this pointer points to.
+    pub const fn address(self) -> u32 {
+        self.address
+    }
+
+    /// Checks whether this pointer is null.
+    pub const fn is_null(self) -> bool {
+        self.address == 0
+    }
+
+    /// Returns a pointer to a value of type `U`, located `bytes` bytes after this one.
+    ///
+    /// This is mostly useful to point to a field of a struct.
+    pub const fn offset<U>(self, bytes: u32) -> EmuPtr<C, U> {
+        EmuPtr::new(self.address.wrapping_add(bytes))
+    }
+
+    /// Returns a pointer to the `i`-th element of an array starting at this pointer.
+    pub const fn index(self, i: u32) -> Self {
+        Self::new(self.address.wrapping_add(i.wrapping_mul(size_of::<T>() as u32)))
+    }
+}
+
+impl<C, T> Clone for EmuPtr<C, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C, T> Copy for EmuPtr<C, T> {}
+
+impl<C, T> PartialEq for EmuPtr<C, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.address == other.address
+    }
+}
+
+impl<C, T> Eq for EmuPtr<C, T> {}
+
+impl<C, T> Hash for EmuPtr<C, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.address.hash(state)
+    }
+}
+
+impl<C, T> Default for EmuPtr<C, T> {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl<C, T> fmt::Debug for EmuPtr<C, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ptr({:#X})", self.address)
+    }
+}
+
+impl<C, T> FromEndian for EmuPtr<C, T> {
+    fn from_endian(&self, endian: Endian) -> Self {
+        Self::new(self.address.from_endian(endian))
+    }
+}
+
+// SAFETY: the struct is `repr(transparent)` over a `u32`, as `PhantomData` is zero-sized
+// and has an alignment of 1, so it has no padding and every bit pattern is valid.
+unsafe impl<C, T> Zeroable for EmuPtr<C, T> {}
+unsafe impl<C: 'static, T: 'static> Pod for EmuPtr<C, T> {}
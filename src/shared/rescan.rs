@@ -0,0 +1,86 @@
+use asr::Address;
+
+/// Controls how often the emulated RAM is looked for while it can't be found.
+///
+/// Intervals are expressed in calls to [`Emulator::update`](super::Emulator::update), which are
+/// expected to happen once per tick. After each failed lookup the interval is doubled,
+/// up to `max_interval`. It goes back to zero once the RAM is found, and starts over from
+/// `initial_interval` when a lookup runs against different modules than the previous one
+/// (for example after Retroarch loaded a different core).
+///
+/// The global instance of each console also applies it to its attempts to attach while no emulator is running.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RescanPolicy {
+    /// Number of updates skipped after the first failed lookup.
+    pub initial_interval: u32,
+    /// Upper bound for the number of updates skipped between two lookups.
+    pub max_interval: u32,
+}
+
+impl RescanPolicy {
+    /// Looks for the emulated RAM on every update.
+    pub const EVERY_UPDATE: Self = Self { initial_interval: 0, max_interval: 0 };
+
+    /// The policy used unless configured otherwise.
+    pub const DEFAULT: Self = Self { initial_interval: 1, max_interval: 64 };
+}
+
+impl Default for RescanPolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Keeps track of the failed lookups and of the modules they were performed against.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Backoff {
+    policy: RescanPolicy,
+    interval: u32,
+    remaining: u32,
+    modules: [Option<Address>; 2],
+}
+
+impl Backoff {
+    pub const fn new(policy: RescanPolicy) -> Self {
+        Self { policy, interval: 0, remaining: 0, modules: [None; 2] }
+    }
+
+    pub fn set_policy(&mut self, policy: RescanPolicy) {
+        self.policy = policy;
+        self.reset();
+    }
+
+    /// Returns true if a lookup should be performed on this update.
+    ///
+    /// This is checked before anything about the process is looked up, so that skipped updates stay cheap.
+    pub fn should_scan(&mut self) -> bool {
+        if self.remaining == 0 {
+            return true
+        }
+
+        self.remaining -= 1;
+        false
+    }
+
+    /// Records the modules a lookup is about to run against, so that the interval starts over
+    /// if they changed since the previous lookup.
+    pub fn track_modules(&mut self, modules: [Option<Address>; 2]) {
+        if modules != self.modules {
+            self.modules = modules;
+            self.interval = 0;
+        }
+    }
+
+    pub fn failed(&mut self) {
+        self.interval = match self.interval {
+            0 => self.policy.initial_interval,
+            interval => interval.saturating_mul(2),
+        }.min(self.policy.max_interval);
+        self.remaining = self.interval;
+    }
+
+    pub fn reset(&mut self) {
+        self.interval = 0;
+        self.remaining = 0;
+    }
+}
@@ -0,0 +1,85 @@
+/// Maximum number of entries that can be preferred or denied by a [`Selection`].
+pub const MAX_SELECTION_LEN: usize = 16;
+
+/// Restricts and reorders the choices tried by this crate, such as the emulators
+/// to attach to or the Retroarch cores to hook to.
+///
+/// Unless told otherwise, every supported choice is tried in the order defined by this crate.
+/// Preferred choices are tried first, in the order they have been added, and denied ones are never tried.
+///
+/// ```ignore
+/// let selection = Selection::any()
+///     .prefer(EmulatorType::Duckstation)
+///     .deny(EmulatorType::Retroarch);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Selection<E> {
+    preferred: [Option<E>; MAX_SELECTION_LEN],
+    denied: [Option<E>; MAX_SELECTION_LEN],
+    exclusive: bool,
+}
+
+impl<E: Copy + PartialEq> Selection<E> {
+    /// Tries every supported choice, in the default order.
+    pub const fn any() -> Self {
+        Self { preferred: [None; MAX_SELECTION_LEN], denied: [None; MAX_SELECTION_LEN], exclusive: false }
+    }
+
+    /// Only tries the provided choices, in the order they are listed.
+    ///
+    /// Providing a single choice forces it to be used.
+    pub fn only(choices: &[E]) -> Self {
+        let mut selection = Self { exclusive: true, ..Self::any() };
+        for &choice in choices {
+            selection = selection.prefer(choice)
+        }
+        selection
+    }
+
+    /// Tries the provided choice before any non-preferred one.
+    pub fn prefer(mut self, choice: E) -> Self {
+        push(&mut self.preferred, choice);
+        self
+    }
+
+    /// Never tries the provided choice.
+    pub fn deny(mut self, choice: E) -> Self {
+        push(&mut self.denied, choice);
+        self
+    }
+
+    /// Checks whether the provided choice can be tried.
+    pub fn allows(&self, choice: E) -> bool {
+        !self.denied.contains(&Some(choice)) && (!self.exclusive || self.preferred.contains(&Some(choice)))
+    }
+
+    /// Iterates over the provided entries, in the order they should be tried,
+    /// skipping the ones that are not allowed.
+    pub fn apply<'a, T>(&'a self, entries: &'a [T], key: fn(&T) -> E) -> impl Iterator<Item = &'a T> + 'a {
+        let preferred = self.preferred.iter()
+            .map_while(|choice| *choice)
+            .flat_map(move |choice| entries.iter().filter(move |entry| key(entry) == choice));
+
+        let others = entries.iter()
+            .filter(move |entry| !self.exclusive && !self.preferred.contains(&Some(key(entry))));
+
+        preferred.chain(others).filter(move |entry| !self.denied.contains(&Some(key(entry))))
+    }
+}
+
+impl<E: Copy + PartialEq> Default for Selection<E> {
+    fn default() -> Self {
+        Self::any()
+    }
+}
+
+/// Appends a choice to a list, unless it's already there or the list is full.
+fn push<E: Copy + PartialEq>(list: &mut [Option<E>], choice: E) {
+    if list.contains(&Some(choice)) {
+        return
+    }
+
+    if let Some(slot) = list.iter_mut().find(|slot| slot.is_none()) {
+        *slot = Some(choice)
+    }
+}
@@ -0,0 +1,115 @@
+use core::{fmt, str};
+mod table;
+
+/// Text encoded in Shift-JIS, as stored by most Japanese games.
+///
+/// The decoding follows code page 932, the variant used by Windows, which also covers the NEC and IBM extensions
+/// found in many games. Bytes that can't be decoded are replaced by `U+FFFD`, and the text ends at the first NUL byte.
+///
+/// ```ignore
+/// let name = ps1::read_string::<16>(0x80012345)?;
+/// let mut buf = [0; 48];
+/// let name = ShiftJis::new(name.as_bytes()).decode_into(&mut buf);
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ShiftJis<'a>(&'a [u8]);
+
+impl<'a> ShiftJis<'a> {
+    /// Wraps the provided Shift-JIS encoded bytes.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        Self(&bytes[..len])
+    }
+
+    /// Returns the encoded bytes, up to the first NUL byte.
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Iterates over the decoded characters.
+    pub fn chars(&self) -> Chars<'a> {
+        Chars(self.0)
+    }
+
+    /// Decodes the text as UTF-8 into the provided buffer, returning the decoded string.
+    ///
+    /// If the buffer is too small, the text is cut after the last character that fits.
+    /// Each character takes up to 3 bytes once decoded, so a buffer 3 times as large as the encoded text is always enough.
+    pub fn decode_into<'b>(&self, buf: &'b mut [u8]) -> &'b str {
+        let mut len = 0;
+
+        for c in self.chars() {
+            let Some(dst) = buf.get_mut(len..len + c.len_utf8()) else { break };
+            c.encode_utf8(dst);
+            len += c.len_utf8();
+        }
+
+        // Only whole characters have been written
+        str::from_utf8(&buf[..len]).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for ShiftJis<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+        self.chars().try_for_each(|c| f.write_char(c))
+    }
+}
+
+impl fmt::Debug for ShiftJis<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+        f.write_char('"')?;
+        self.chars().flat_map(char::escape_debug).try_for_each(|c| f.write_char(c))?;
+        f.write_char('"')
+    }
+}
+
+/// Iterator over the characters of a [`ShiftJis`] text.
+#[derive(Clone)]
+pub struct Chars<'a>(&'a [u8]);
+
+impl Iterator for Chars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let (&lead, rest) = self.0.split_first()?;
+        self.0 = rest;
+
+        let c = match lead {
+            0x00..=0x7F => lead as char,
+            // Half-width katakana
+            0xA1..=0xDF => char::from_u32(0xFF61 + (lead - 0xA1) as u32).unwrap_or(char::REPLACEMENT_CHARACTER),
+            _ => {
+                let Some(&trail) = self.0.first() else { return Some(char::REPLACEMENT_CHARACTER) };
+                let Some(index) = table_index(lead, trail) else { return Some(char::REPLACEMENT_CHARACTER) };
+
+                // The trail byte is only consumed if it's a valid one, so that a stray lead byte doesn't eat the next character
+                self.0 = &self.0[1..];
+                match table::TABLE[index] {
+                    0 => char::REPLACEMENT_CHARACTER,
+                    c => char::from_u32(c as u32).unwrap_or(char::REPLACEMENT_CHARACTER),
+                }
+            },
+        };
+
+        Some(c)
+    }
+}
+
+/// Returns the position of a double-byte character inside the decoding table.
+fn table_index(lead: u8, trail: u8) -> Option<usize> {
+    let row = match lead {
+        0x81..=0x9F => lead - 0x81,
+        0xE0..=0xFC => lead - 0xE0 + 31,
+        _ => return None,
+    };
+
+    let column = match trail {
+        0x40..=0x7E => trail - 0x40,
+        0x80..=0xFC => trail - 0x41,
+        _ => return None,
+    };
+
+    Some(row as usize * 188 + column as usize)
+}
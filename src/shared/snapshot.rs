@@ -0,0 +1,148 @@
+use asr::Address;
+use core::{cell::UnsafeCell, sync::atomic::{AtomicBool, Ordering}};
+use bytemuck::CheckedBitPattern;
+use super::{Console, MemorySource};
+use crate::Error;
+
+/// Maximum number of guest ranges a snapshot can hold.
+pub const MAX_SNAPSHOT_RANGES: usize = 8;
+
+/// A statically allocated buffer of `N` bytes, which can hold the snapshot of the emulated memory.
+///
+/// As snapshots are kept across emulators being attached and detached, their buffer has to live
+/// for the whole runtime of the auto splitter. This type provides one without any `unsafe` code:
+///
+/// ```ignore
+/// static SNAPSHOT: SnapshotBuffer<0x200000> = SnapshotBuffer::new();
+///
+/// if let Some(buf) = SNAPSHOT.take() {
+///     ps1::enable_snapshot(buf, &[ps1::WHOLE_RAM]);
+/// }
+/// ```
+pub struct SnapshotBuffer<const N: usize> {
+    buf: UnsafeCell<[u8; N]>,
+    taken: AtomicBool,
+}
+
+// SAFETY: the buffer is only ever accessed through the single reference handed out by `take`.
+unsafe impl<const N: usize> Sync for SnapshotBuffer<N> {}
+
+impl<const N: usize> SnapshotBuffer<N> {
+    /// Creates a zeroed buffer.
+    pub const fn new() -> Self {
+        Self { buf: UnsafeCell::new([0; N]), taken: AtomicBool::new(false) }
+    }
+
+    /// Returns the buffer, or `None` if it has already been taken.
+    // The flag makes sure a single mutable reference is ever handed out
+    #[allow(clippy::mut_from_ref)]
+    pub fn take(&'static self) -> Option<&'static mut [u8]> {
+        if self.taken.swap(true, Ordering::AcqRel) {
+            return None
+        }
+
+        // SAFETY: the flag guarantees this reference is only created once
+        Some(unsafe { &mut *self.buf.get() })
+    }
+}
+
+impl<const N: usize> Default for SnapshotBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A guest range copied into the snapshot buffer.
+#[derive(Copy, Clone, Debug)]
+struct Range {
+    address: u32,
+    len: usize,
+    /// Position of the range inside the buffer.
+    offset: usize,
+    /// Host address the range has been copied from during the last refresh.
+    host: Address,
+}
+
+/// A local copy of some guest ranges, taken once per update so that reads don't have to reach the emulator.
+pub(crate) struct Snapshot {
+    buf: &'static mut [u8],
+    ranges: [Option<Range>; MAX_SNAPSHOT_RANGES],
+    valid: bool,
+}
+
+impl Snapshot {
+    /// Lays out the provided guest ranges inside the buffer.
+    ///
+    /// Ranges that don't fit in the buffer, or beyond [`MAX_SNAPSHOT_RANGES`], are left out and keep being read live.
+    pub fn new(buf: &'static mut [u8], ranges: &[(u32, usize)]) -> Self {
+        let mut layout = [None; MAX_SNAPSHOT_RANGES];
+        let mut slots = layout.iter_mut();
+        let mut offset = 0;
+
+        for &(address, len) in ranges.iter().filter(|range| range.1 != 0) {
+            // Later ranges may still fit in the space left
+            if offset + len > buf.len() {
+                continue
+            }
+
+            let Some(slot) = slots.next() else { break };
+            *slot = Some(Range { address, len, offset, host: Address(0) });
+            offset += len;
+        }
+
+        Self { buf, ranges: layout, valid: false }
+    }
+
+    /// Returns the buffer, so it can be reused.
+    pub fn into_buffer(self) -> &'static mut [u8] {
+        self.buf
+    }
+
+    /// Copies every range from the emulated memory. If any of them fails, the whole snapshot
+    /// is invalidated and reads go back to the emulator until the next refresh.
+    pub fn refresh<C: Console, P: MemorySource>(&mut self, process: &P, ram: &C::Ram) -> bool {
+        self.valid = self.ranges.iter_mut().flatten().all(|range| {
+            let Ok(host) = C::translate(ram, range.address, range.len) else { return false };
+            range.host = host;
+            process.read_into_buf(host, &mut self.buf[range.offset..range.offset + range.len]).is_some()
+        });
+
+        self.valid
+    }
+
+    pub fn invalidate(&mut self) {
+        self.valid = false
+    }
+
+    /// Returns the copied bytes found at the provided host address, if the snapshot holds all of them.
+    fn lookup(&self, host: Address, size: usize) -> Option<&[u8]> {
+        if !self.valid {
+            return None
+        }
+
+        self.ranges.iter().flatten().find_map(|range| {
+            let start = usize::try_from(host.0.checked_sub(range.host.0)?).ok()?;
+            if start.checked_add(size)? > range.len {
+                return None
+            }
+
+            let start = range.offset + start;
+            Some(&self.buf[start..start + size])
+        })
+    }
+
+    /// Reads a value from the snapshot. Returns `None` if it's not held by the snapshot, so it has to be read live.
+    pub fn read<T: CheckedBitPattern>(&self, host: Address, address: u32) -> Option<Result<T, Error>> {
+        let size = core::mem::size_of::<T>();
+        let bytes = self.lookup(host, size)?;
+
+        Some(bytemuck::checked::try_pod_read_unaligned(bytes).map_err(|_| Error::ReadFailed { address, size }))
+    }
+
+    /// Fills a buffer from the snapshot. Returns false if it's not held by the snapshot, so it has to be read live.
+    pub fn read_into_buf(&self, host: Address, buf: &mut [u8]) -> bool {
+        let Some(bytes) = self.lookup(host, buf.len()) else { return false };
+        buf.copy_from_slice(bytes);
+        true
+    }
+}
@@ -0,0 +1,169 @@
+use core::{any::TypeId, fmt, marker::PhantomData};
+use crate::Error;
+
+/// Maximum number of values a single emulator can watch.
+pub const MAX_WATCHES: usize = 32;
+
+/// Maximum size, in bytes, of a watched value.
+pub const MAX_WATCH_SIZE: usize = 32;
+
+/// A handle to a value of type `T`, stored in the memory of the console `C` and read again on every update.
+///
+/// Each console exposes it with its own alias, such as `ps1::Watch<T>`, along with the methods returning
+/// the values read during the last two updates.
+pub struct Watch<C, T> {
+    index: usize,
+    generation: u32,
+    _marker: PhantomData<fn() -> (C, T)>,
+}
+
+impl<C, T> Clone for Watch<C, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C, T> Copy for Watch<C, T> {}
+
+impl<C, T> PartialEq for Watch<C, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<C, T> Eq for Watch<C, T> {}
+
+impl<C, T> fmt::Debug for Watch<C, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Watch({}, generation {})", self.index, self.generation)
+    }
+}
+
+/// Reads a watched value into a buffer of the value's size, applying the console's byte order conversions.
+pub(crate) type ReadFn<E> = fn(&E, u32, &mut [u8]) -> Result<(), Error>;
+
+/// Stores the bytes of a value read from the emulated memory into `buf`, which has the size of the value.
+#[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+pub(crate) fn store<T: bytemuck::Pod>(buf: &mut [u8], value: Result<T, Error>) -> Result<(), Error> {
+    buf.copy_from_slice(bytemuck::bytes_of(&value?));
+    Ok(())
+}
+
+struct Entry<E> {
+    address: u32,
+    type_id: TypeId,
+    size: usize,
+    read: ReadFn<E>,
+    old: [u8; MAX_WATCH_SIZE],
+    current: [u8; MAX_WATCH_SIZE],
+    /// Whether a value has been read since the last reset.
+    has_value: bool,
+}
+
+impl<E> Clone for Entry<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for Entry<E> {}
+
+/// The values watched by an emulator `E`, along with the last two values read for each of them.
+pub(crate) struct WatchSet<E> {
+    entries: [Option<Entry<E>>; MAX_WATCHES],
+    /// How many times each slot has been freed, so that handles to a removed value don't reach
+    /// the value registered in its place.
+    generations: [u32; MAX_WATCHES],
+}
+
+impl<E> WatchSet<E> {
+    pub const fn new() -> Self {
+        Self { entries: [None; MAX_WATCHES], generations: [0; MAX_WATCHES] }
+    }
+
+    /// Registers a value, returning `None` if the set is full or the value is larger than [`MAX_WATCH_SIZE`].
+    #[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+    pub fn add<C, T: bytemuck::Pod>(&mut self, address: u32, read: ReadFn<E>) -> Option<Watch<C, T>> {
+        let size = core::mem::size_of::<T>();
+        if size > MAX_WATCH_SIZE {
+            return None
+        }
+
+        let index = self.entries.iter().position(Option::is_none)?;
+        self.entries[index] = Some(Entry {
+            address,
+            type_id: TypeId::of::<T>(),
+            size,
+            read,
+            old: [0; MAX_WATCH_SIZE],
+            current: [0; MAX_WATCH_SIZE],
+            has_value: false,
+        });
+
+        Some(Watch { index, generation: self.generations[index], _marker: PhantomData })
+    }
+
+    pub fn remove<C, T: 'static>(&mut self, watch: Watch<C, T>) {
+        if self.entry::<C, T>(watch).is_some() {
+            self.free(watch.index)
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for index in 0..MAX_WATCHES {
+            if self.entries[index].is_some() {
+                self.free(index)
+            }
+        }
+    }
+
+    fn free(&mut self, index: usize) {
+        self.entries[index] = None;
+        self.generations[index] = self.generations[index].wrapping_add(1);
+    }
+
+    /// Forgets every value read so far, keeping the registered values.
+    pub fn reset(&mut self) {
+        for entry in self.entries.iter_mut().flatten() {
+            entry.has_value = false
+        }
+    }
+
+    /// Reads every registered value again. Values that can't be read are reset.
+    pub fn refresh(&mut self, emulator: &E) {
+        for entry in self.entries.iter_mut().flatten() {
+            let mut buf = [0; MAX_WATCH_SIZE];
+
+            if (entry.read)(emulator, entry.address, &mut buf[..entry.size]).is_err() {
+                entry.has_value = false;
+                continue
+            }
+
+            entry.old = if entry.has_value { entry.current } else { buf };
+            entry.current = buf;
+            entry.has_value = true;
+        }
+    }
+
+    /// Returns the values read during the last two updates, or `None` if the last read failed.
+    ///
+    /// After the first successful read, both values are the same.
+    #[cfg(any(feature = "ps1", feature = "genesis", feature = "wii"))]
+    pub fn pair<C, T: bytemuck::Pod>(&self, watch: Watch<C, T>) -> Option<asr::watcher::Pair<T>> {
+        let entry = self.entry(watch).filter(|entry| entry.has_value)?;
+
+        Some(asr::watcher::Pair {
+            old: bytemuck::pod_read_unaligned(&entry.old[..entry.size]),
+            current: bytemuck::pod_read_unaligned(&entry.current[..entry.size]),
+        })
+    }
+
+    /// Returns the entry of a watch, as long as it has not been removed, even if its slot has been reused since.
+    fn entry<C, T: 'static>(&self, watch: Watch<C, T>) -> Option<&Entry<E>> {
+        if self.generations.get(watch.index) != Some(&watch.generation) {
+            return None
+        }
+
+        self.entries[watch.index].as_ref().filter(|entry| entry.type_id == TypeId::of::<T>())
+    }
+}
@@ -1,192 +1,442 @@
-use core::fmt::Error;
 use asr::{
-    Address, Process,
+    Address,
+    string::ArrayCString,
     sync::Mutex,
+    watcher::Pair,
     primitives::dynamic_endian::{FromEndian, Endian},
 };
-use bytemuck::CheckedBitPattern;
+use bytemuck::{CheckedBitPattern, Pod};
+use crate::{Error, shared::{Console, EmulatorBackend, MemorySource, MemoryDomain, EmuPtr, NamePattern, Global, RescanPolicy, update_global, status_global, set_rescan_policy_global, set_selection_global, set_attach_policy_global, read_global, enable_snapshot_global, disable_snapshot_global, refresh_snapshot_global, invalidate_snapshot_global, query_global, memory_domains_global, watches_global, store, AttachStatus, AttachPolicy, Candidate, Selection}};
 mod dolphin;
 
-static STATE: Mutex<State> = Mutex::new(State {
-    proc: None,
-});
+static STATE: Mutex<Global<Wii>> = Mutex::new(Global::new());
 
-struct State {
-    proc: Option<ProcessInfo>,
-}
+/// The Nintendo Wii.
+pub struct Wii;
 
-pub struct ProcessInfo {
-    emulator_type: Emulator,
-    emulator_process: Process,
-    mem_1: Option<Address>,
-    mem_2: Option<Address>,
-    endianness: Endian,
+/// Addresses of `MEM1` and `MEM2`, as found by the backend.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Ram {
+    mem_1: Address,
+    mem_2: Address,
 }
 
-impl ProcessInfo {
-    fn attach_process() -> Option<Self> {
-        let (emulator_type, Some(emulator_process)) = PROCESS_NAMES.iter()
-            .map(|name| (name.1, Process::attach(name.0)))
-            .find(|p| p.1.is_some())? else { return None };
+impl Console for Wii {
+    const NAME: &'static str = "Wii";
+
+    type EmulatorType = EmulatorType;
+    type Backend = Backend;
+    type Ram = Ram;
+
+    const PROCESS_NAMES: &'static [(NamePattern, EmulatorType)] = &PROCESS_NAMES;
 
-        Some(Self {
-            emulator_type,
-            emulator_process,
-            mem_1: None,
-            mem_2: None,
-            endianness: Endian::Big,  // The only emulator worth mentioning for the Wii (Dolphin), uses Big Endian
-        })
+    fn backend(emulator_type: EmulatorType) -> Backend {
+        match emulator_type {
+            EmulatorType::Dolphin => Backend::Dolphin(Default::default()),
+        }
     }
 
-    fn look_for_wram(&mut self) -> (Option<Address>, Option<Address>) {
-        match self.emulator_type {
-            Emulator::Dolphin => dolphin::dolphin(self),
-        }      
+    fn translate(ram: &Ram, address: u32, size: usize) -> Result<Address, Error> {
+        let (base, offset, region_size) = if address <= 0x017FFFFF {
+            (ram.mem_1, address, 0x1800000)
+        } else if is_mem_1(address) {
+            (ram.mem_1, address & 0x0FFFFFFF, 0x1800000)
+        } else if is_mem_2(address) {
+            (ram.mem_2, address & 0x0FFFFFFF, 0x4000000)
+        } else {
+            return Err(Error::OutOfRange { address, size })
+        };
+
+        if offset as usize + size > region_size {
+            return Err(Error::OutOfRange { address, size })
+        }
+
+        Ok(Address(base.0 + offset as u64))
     }
 
-    fn keep_alive(&self) -> bool {
-        match self.emulator_type {
-            Emulator::Dolphin => dolphin::keep_alive(self),
-        }      
+    fn memory_domain(ram: &Ram, index: usize) -> Option<MemoryDomain> {
+        match index {
+            0 => Some(MemoryDomain { name: "MEM1", guest_address: 0x80000000, size: 0x1800000, host_address: ram.mem_1, endian: Endian::Big }),
+            1 => Some(MemoryDomain { name: "MEM2", guest_address: 0x90000000, size: 0x4000000, host_address: ram.mem_2, endian: Endian::Big }),
+            _ => None,
+        }
     }
 }
 
-impl State {
-    fn init(&mut self) -> bool {
-        if self.proc.is_none() {
-            self.proc = ProcessInfo::attach_process()
+/// Whether `address` is in `MEM1`, through either its cached (`0x80000000`) or uncached (`0xC0000000`) mirror.
+const fn is_mem_1(address: u32) -> bool {
+    matches!(address, 0x80000000..=0x817FFFFF | 0xC0000000..=0xC17FFFFF)
+}
+
+/// Whether `address` is in `MEM2`, through either its cached (`0x90000000`) or uncached (`0xD0000000`) mirror.
+const fn is_mem_2(address: u32) -> bool {
+    matches!(address, 0x90000000..=0x93FFFFFF | 0xD0000000..=0xD3FFFFFF)
+}
+
+/// A handle to a running Wii emulator.
+pub type Emulator<P = asr::Process> = crate::shared::Emulator<Wii, P>;
+
+impl<P: MemorySource> Emulator<P> {
+    /// Reads any value from the emulated RAM.
+    ///
+    /// See [`read`] for details about the accepted addresses.
+    pub fn read<T: CheckedBitPattern + FromEndian>(&self, address: u32) -> Result<T, Error> {
+        // The only emulator worth mentioning for the Wii (Dolphin), uses Big Endian
+        Ok(self.read_raw::<T>(address)?.from_endian(Endian::Big))
+    }
+
+    /// Follows a path of pointers stored in the emulated RAM, returning the address it leads to.
+    ///
+    /// See [`read_pointer_path`] for details.
+    pub fn resolve_pointer_path(&self, base: u32, offsets: &[u32]) -> Result<u32, Error> {
+        self.resolve_pointer_path_with(base, offsets, |emulator, address| emulator.read::<u32>(address))
+    }
+
+    /// Follows a path of pointers stored in the emulated RAM, then reads the value it leads to.
+    ///
+    /// See [`read_pointer_path`] for details.
+    pub fn read_pointer_path<T: CheckedBitPattern + FromEndian>(&self, base: u32, offsets: &[u32]) -> Result<T, Error> {
+        self.read(self.resolve_pointer_path(base, offsets)?)
+    }
+
+    /// Fills a slice with values read from the emulated RAM.
+    ///
+    /// See [`read_into_slice`] for details about the accepted addresses.
+    pub fn read_into_slice<T: Pod + FromEndian>(&self, address: u32, slice: &mut [T]) -> Result<(), Error> {
+        self.read_raw_into_slice(address, slice)?;
+        for value in slice.iter_mut() {
+            *value = value.from_endian(Endian::Big)
         }
+        Ok(())
+    }
 
-        let Some(game) = &mut self.proc else {
-            return false
-        };
+    /// Reads an array of values from the emulated RAM.
+    ///
+    /// See [`read_into_slice`] for details about the accepted addresses.
+    pub fn read_array<T: Pod + FromEndian, const N: usize>(&self, address: u32) -> Result<[T; N], Error> {
+        let mut array = [T::zeroed(); N];
+        self.read_into_slice(address, &mut array)?;
+        Ok(array)
+    }
 
-        if !game.emulator_process.is_open() {
-            self.proc = None;
-            return false
+    /// Reads a fixed-length string from the emulated RAM.
+    ///
+    /// See [`read_string`] for details.
+    pub fn read_string<const N: usize>(&self, address: u32) -> Result<ArrayCString<N>, Error> {
+        let mut string = ArrayCString::new();
+        self.read_into_slice(address, bytemuck::bytes_of_mut(&mut string))?;
+        Ok(string)
+    }
+
+    /// Registers a value to be read on every [`Emulator::update`], returning a handle to its last two values.
+    ///
+    /// See [`watch`] for details.
+    pub fn watch<T: Pod + FromEndian>(&mut self, address: u32) -> Option<Watch<T>> {
+        self.watches_mut().add(address, |emulator, address, buf| store(buf, emulator.read::<T>(address)))
+    }
+
+    /// Reads any value from `MEM1`.
+    ///
+    /// See [`read_from_mem_1`] for details about the accepted addresses.
+    pub fn read_from_mem_1<T: CheckedBitPattern + FromEndian>(&self, address: u32) -> Result<T, Error> {
+        if address > 0x017FFFFF && !is_mem_1(address) {
+            return Err(Error::OutOfRange { address, size: core::mem::size_of::<T>() })
         }
 
-        if game.mem_1.is_none() || game.mem_2.is_none() {
-            (game.mem_1, game.mem_2) = game.look_for_wram();
-            
-            if game.mem_1.is_none() || game.mem_2.is_none() {
-                return false
-            }
+        self.read(address)
+    }
+
+    /// Reads any value from `MEM2`.
+    ///
+    /// See [`read_from_mem_2`] for details about the accepted addresses.
+    pub fn read_from_mem_2<T: CheckedBitPattern + FromEndian>(&self, address: u32) -> Result<T, Error> {
+        if !is_mem_2(address) {
+            return Err(Error::OutOfRange { address, size: core::mem::size_of::<T>() })
         }
 
-        if !game.keep_alive() {
-            game.mem_1 = None;
-            game.mem_2 = None;
+        self.read(address)
+    }
+}
+
+/// A pointer to a value stored in the Wii's memory, which can be used as a field of structs read from it.
+pub type Ptr<T> = EmuPtr<Wii, T>;
+
+impl<T: CheckedBitPattern + FromEndian> Ptr<T> {
+    /// Reads the value this pointer points to, through the global emulator instance.
+    ///
+    /// Null pointers make this method return `Err(Error::NullPointer)`.
+    pub fn read(self) -> Result<T, Error> {
+        read_global(&STATE, |emulator| self.read_from(emulator))
+    }
+
+    /// Reads the value this pointer points to, through the provided emulator.
+    ///
+    /// Null pointers make this method return `Err(Error::NullPointer)`. As pointers stored by games hold
+    /// virtual addresses, those which don't point inside `MEM1` or `MEM2` return `Err(Error::OutOfRange)`.
+    pub fn read_from<P: MemorySource>(self, emulator: &Emulator<P>) -> Result<T, Error> {
+        if self.is_null() {
+            return Err(Error::NullPointer { address: 0 })
         }
 
-        game.mem_1.is_some() && game.mem_2.is_some()
+        let address = self.address();
+        if is_mem_1(address) {
+            emulator.read_from_mem_1(address)
+        } else if is_mem_2(address) {
+            emulator.read_from_mem_2(address)
+        } else {
+            Err(Error::OutOfRange { address, size: core::mem::size_of::<T>() })
+        }
+    }
+}
+
+/// A value stored in the Wii's memory, read again on every update.
+pub type Watch<T> = crate::shared::Watch<Wii, T>;
+
+impl<T: Pod + FromEndian> Watch<T> {
+    /// Returns the values read by the last two calls to [`update`].
+    ///
+    /// Returns `None` if the last read failed, or if no emulator is attached. Both values are the same
+    /// after the first successful read, as well as after the emulator has been attached again or the RAM has moved.
+    pub fn pair(self) -> Option<Pair<T>> {
+        query_global(&STATE, |emulator| emulator.watches().pair(self)).flatten()
+    }
+
+    /// Returns the values read by the last two updates of the provided emulator.
+    ///
+    /// See [`Watch::pair`] for details.
+    pub fn pair_from<P: MemorySource>(self, emulator: &Emulator<P>) -> Option<Pair<T>> {
+        emulator.watches().pair(self)
     }
 }
 
 /// Calls the internal routines needed in order to hook to the target emulator and find the address of the emulated RAM.
-/// 
+///
 /// Returns true if successful, false otherwise.
-/// 
+///
 /// As of now, the only supported emulator is Dolphin.
+///
+/// This is a thin wrapper around a global [`Emulator`] instance.
 pub fn update() -> bool {
-    let state = &mut STATE.lock();
-    state.init()
+    update_global(&STATE, |_| ())
+}
+
+/// Returns the result of the last [`update`], telling apart a missing emulator,
+/// an unsupported core and emulated RAM that has yet to be found.
+pub fn status() -> AttachStatus<EmulatorType> {
+    status_global(&STATE)
+}
+
+/// Sets how often [`update`] looks for the emulated RAM while it can't be found.
+///
+/// By default, [`RescanPolicy::DEFAULT`] is used.
+pub fn set_rescan_policy(policy: RescanPolicy) {
+    set_rescan_policy_global(&STATE, policy)
+}
+
+/// Restricts which emulators [`update`] can attach to, and sets the order they are tried in.
+///
+/// If the emulator currently attached is not allowed anymore, it gets detached.
+pub fn set_emulator_selection(selection: Selection<EmulatorType>) {
+    set_selection_global(&STATE, selection)
+}
+
+/// Sets which process [`update`] attaches to, when several supported emulators are running.
+///
+/// By default, [`AttachPolicy::FirstFound`] is used.
+pub fn set_attach_policy(policy: AttachPolicy) {
+    set_attach_policy_global(&STATE, policy)
+}
+
+/// Lists every supported emulator currently running, along with its process ID.
+///
+/// Any of them can be attached to through [`set_attach_policy`], using [`AttachPolicy::Pid`].
+pub fn candidates() -> impl Iterator<Item = Candidate<EmulatorType>> {
+    Emulator::candidates(&Selection::any())
+}
+
+/// Lists the regions of the emulated memory, along with the addresses they are stored at inside the emulator.
+///
+/// This is meant for advanced uses, such as asr's own pointer paths or signature scans. Nothing is listed
+/// until [`update`] has found the emulated RAM.
+pub fn memory_domains() -> impl Iterator<Item = MemoryDomain> {
+    memory_domains_global(&STATE)
+}
+
+/// Serves every read from a local copy of the provided guest ranges, taken once per [`update`].
+///
+/// Each range is an address, in any of the formats accepted by [`read`], along with its length in bytes.
+/// Ranges are copied one after the other into `buf`, which can be obtained from a
+/// [`SnapshotBuffer`](crate::shared::SnapshotBuffer). Reads that aren't entirely contained in a single range,
+/// as well as ranges that don't fit in the buffer, keep being performed on the emulator.
+///
+/// This is meant for auto splitters reading many values on each tick, which then need a single read per range.
+/// The snapshot is kept across emulators being attached and detached.
+pub fn enable_snapshot(buf: &'static mut [u8], ranges: &[(u32, usize)]) {
+    enable_snapshot_global(&STATE, buf, ranges)
+}
+
+/// Goes back to reading every value from the emulator, returning the buffer used by the snapshot.
+pub fn disable_snapshot() -> Option<&'static mut [u8]> {
+    disable_snapshot_global(&STATE)
+}
+
+/// Copies the guest ranges into the snapshot again, outside of [`update`].
+///
+/// Returns false if no snapshot is enabled, or if it could not be refreshed.
+pub fn refresh_snapshot() -> bool {
+    refresh_snapshot_global(&STATE)
+}
+
+/// Discards the content of the snapshot, so values are read from the emulator until the next [`update`] or [`refresh_snapshot`].
+pub fn invalidate_snapshot() {
+    invalidate_snapshot_global(&STATE)
 }
 
 /// Reads any value from the emulated RAM.
-/// 
+///
 /// The address provided is meant to be the mapped address used on the original, big-endian system.
 /// The call will automatically convert the address provided to its corresponding offset from
 /// `MEM1` or `MEM2` and read the value, converting it to little endian if necessary.
-/// 
+///
 /// The address provided has to match a mapped memory address on the original Wii:
-/// - Valid addresses for `MEM1` range from `0x80000000` to `0x817FFFFF`
-/// - Valid addresses for `MEM2` range from `0x90000000` to `0x93FFFFFF`
-/// 
+/// - Valid addresses for `MEM1` range from `0x80000000` to `0x817FFFFF`, or `0xC0000000` to `0xC17FFFFF` uncached
+/// - Valid addresses for `MEM2` range from `0x90000000` to `0x93FFFFFF`, or `0xD0000000` to `0xD3FFFFFF` uncached
+///
 /// Values below and up to `0x017FFFFF` are assumed to be offsets from `MEM1`'s base address.
-/// Any other invalid value will make this method immediately return `Err()`.
+/// Any other invalid value will make this method immediately return `Err(Error::OutOfRange)`.
 pub fn read<T: CheckedBitPattern + FromEndian>(address: u32) -> Result<T, Error> {
-    if (address > 0x017FFFFF && address < 0x80000000)
-        || (address > 0x817FFFFF && address < 0x90000000)
-        || address > 0x93FFFFFF {
-        return Err(Error)
-    }
+    read_global(&STATE, |emulator| emulator.read(address))
+}
 
-    if address <= 0x017FFFFF || (address >= 0x80000000 && address <= 0x817FFFFF)
-    {
-        read_from_mem_1(address)
-    } else if address >= 0x90000000 && address <= 0x93FFFFFF {
-        read_from_mem_2(address)
-    } else {
-        Err(Error)
-    }
+/// Follows a path of pointers stored in the emulated RAM, then reads the value it leads to.
+///
+/// Starting from `base`, each offset but the last one is added to the current address and the pointer
+/// stored there is read. The last offset is added to the final pointer, and the value is read from there.
+/// Pointers are read as big endian values, and can point either to `MEM1` (`0x80xxxxxx`) or `MEM2` (`0x90xxxxxx`),
+/// or to their uncached mirrors (`0xC0xxxxxx` and `0xD0xxxxxx`).
+///
+/// Null pointers, which are common while games are loading, make this method return `Err(Error::NullPointer)`,
+/// while pointers outside of the emulated RAM make it return `Err(Error::OutOfRange)`.
+pub fn read_pointer_path<T: CheckedBitPattern + FromEndian>(base: u32, offsets: &[u32]) -> Result<T, Error> {
+    read_global(&STATE, |emulator| emulator.read_pointer_path(base, offsets))
+}
+
+/// Fills a slice with values read from the emulated RAM, in a single read,
+/// converting each of them to little endian if necessary.
+///
+/// Addresses are accepted in the same formats as [`read`]. As `MEM1` and `MEM2` are not contiguous,
+/// the whole slice has to fit inside one of them, otherwise this method will immediately return
+/// `Err(Error::OutOfRange)` without reading anything.
+pub fn read_into_slice<T: Pod + FromEndian>(address: u32, slice: &mut [T]) -> Result<(), Error> {
+    read_global(&STATE, |emulator| emulator.read_into_slice(address, slice))
+}
+
+/// Reads an array of values from the emulated RAM, in a single read.
+///
+/// See [`read_into_slice`] for details about the accepted addresses.
+pub fn read_array<T: Pod + FromEndian, const N: usize>(address: u32) -> Result<[T; N], Error> {
+    read_global(&STATE, |emulator| emulator.read_array(address))
+}
+
+/// Reads a string of up to `N` bytes from the emulated RAM, in a single read.
+///
+/// The string ends at the first NUL byte, or after `N` bytes for fixed-length fields that fill all of them.
+/// Japanese games usually store their text in Shift-JIS, which can be decoded with `shared::ShiftJis`
+/// when the `shift-jis` feature is enabled.
+///
+/// Addresses are accepted in the same formats as [`read`].
+pub fn read_string<const N: usize>(address: u32) -> Result<ArrayCString<N>, Error> {
+    read_global(&STATE, |emulator| emulator.read_string(address))
+}
+
+/// Registers a value to be read on every [`update`], replacing the need for a hand-made [`asr::watcher::Watcher`].
+///
+/// The value is read in the same way as [`read`], and the returned handle gives access to the values read
+/// during the last two updates, along with the helpers of [`Pair`]. Registered values are kept across emulators
+/// being attached and detached, but what has been read so far is discarded, as it is when the RAM moves.
+///
+/// Returns `None` if [`MAX_WATCHES`](crate::shared::MAX_WATCHES) values are already registered,
+/// or if the value is larger than [`MAX_WATCH_SIZE`](crate::shared::MAX_WATCH_SIZE).
+pub fn watch<T: Pod + FromEndian>(address: u32) -> Option<Watch<T>> {
+    watches_global(&STATE, |watches| watches.add(address, |emulator: &Emulator, address, buf| store(buf, emulator.read::<T>(address))))
+}
+
+/// Stops reading a value registered with [`watch`].
+pub fn unwatch<T: 'static>(watch: Watch<T>) {
+    watches_global(&STATE, |watches| watches.remove(watch))
+}
+
+/// Stops reading every value registered with [`watch`].
+pub fn clear_watches() {
+    watches_global(&STATE, |watches| watches.clear())
 }
 
 /// Reads any value from `MEM1`.
-/// 
+///
 /// The address provided is meant to be the mapped address used on the original, big-endian system.
 /// The call will automatically convert the address provided to its corresponding offset from
 /// `MEM1` and read the value, converting it to little endian if necessary.
-/// 
-/// The address provided has to match a mapped memory address on the original Wii. 
-/// Valid addresses for `MEM1` range from `0x80000000` to `0x817FFFFF`
-/// 
+///
+/// The address provided has to match a mapped memory address on the original Wii.
+/// Valid addresses for `MEM1` range from `0x80000000` to `0x817FFFFF`, or `0xC0000000` to `0xC17FFFFF` uncached
+///
 /// Values below and up to `0x017FFFFF` are assumed to be offsets from `MEM1`'s base address.
-/// Any other invalid value will make this method immediately return `Err()`.
+/// Any other invalid value will make this method immediately return `Err(Error::OutOfRange)`.
 pub fn read_from_mem_1<T: CheckedBitPattern + FromEndian>(address: u32) -> Result<T, Error> {
-    const SHIFT: u32 = 0x80000000;
-
-    if (address > 0x017FFFFF && address < SHIFT) || address > 0x817FFFFF {
-        return Err(Error)
-    }
-
-    let state = STATE.lock();
-    let Some(proc) = &state.proc else { return Err(Error) };
-    let Some(mem_1) = &proc.mem_1 else { return Err(Error) };
-
-    let mut offset = address;
-
-    if offset >= SHIFT {
-        offset -= SHIFT
-    }
-
-    let Ok(value) = proc.emulator_process.read::<T>(Address(mem_1.0 + offset as u64)) else { return Err(Error) };
-    Ok(value.from_endian(proc.endianness))
+    read_global(&STATE, |emulator| emulator.read_from_mem_1(address))
 }
 
 /// Reads any value from `MEM2`.
-/// 
+///
 /// The address provided is meant to be the mapped address used on the original, big-endian system.
 /// The call will automatically convert the address provided to its corresponding offset from
 /// `MEM2` and read the value, converting it to little endian if necessary.
-/// 
-/// The address provided has to match a mapped memory address on the original Wii. 
-/// Valid addresses for `MEM2` range from `0x90000000` to `0x93FFFFFF`
-/// 
-/// Values below and up to `0x03FFFFFF` are assumed to be offsets from `MEM2`'s base address.
-/// Any other invalid value will make this method immediately return `Err()`.
+///
+/// The address provided has to match a mapped memory address on the original Wii.
+/// Valid addresses for `MEM2` range from `0x90000000` to `0x93FFFFFF`, or `0xD0000000` to `0xD3FFFFFF` uncached
+///
+/// Any other invalid value will make this method immediately return `Err(Error::OutOfRange)`.
 pub fn read_from_mem_2<T: CheckedBitPattern + FromEndian>(address: u32) -> Result<T, Error> {
-    const SHIFT: u32 = 0x90000000;
+    read_global(&STATE, |emulator| emulator.read_from_mem_2(address))
+}
+
+/// Emulator-specific data, such as pointers cached while looking for the emulated RAM.
+pub enum Backend {
+    Dolphin(dolphin::Dolphin),
+}
 
-    if address < SHIFT || address > 0x93FFFFFF {
-        return Err(Error)
+impl EmulatorBackend<Wii> for Backend {
+    fn find_ram<P: MemorySource>(&mut self, proc: &P) -> Option<Ram> {
+        match self {
+            Self::Dolphin(backend) => backend.find_ram(proc),
+        }
     }
 
-    let state = STATE.lock();
-    let Some(proc) = &state.proc else { return Err(Error) };
-    let Some(mem_2) = &proc.mem_2 else { return Err(Error) };
+    fn keep_alive<P: MemorySource>(&mut self, proc: &P, ram: &mut Ram) -> bool {
+        match self {
+            Self::Dolphin(backend) => backend.keep_alive(proc, ram),
+        }
+    }
 
-    let offset = address - SHIFT;
+    fn core_loaded<P: MemorySource>(&self, proc: &P) -> bool {
+        match self {
+            Self::Dolphin(backend) => backend.core_loaded(proc),
+        }
+    }
 
-    let Ok(value) = proc.emulator_process.read::<T>(Address(mem_2.0 + offset as u64)) else { return Err(Error) };
-    Ok(value.from_endian(proc.endianness))
+    fn core_address<P: MemorySource>(&self, proc: &P) -> Option<Address> {
+        match self {
+            Self::Dolphin(backend) => backend.core_address(proc),
+        }
+    }
 }
 
+/// The Wii emulators supported by this crate.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-enum Emulator {
+pub enum EmulatorType {
     Dolphin,
 }
 
-const PROCESS_NAMES: [(&str, Emulator); 1] = [
-    ("Dolphin.exe", Emulator::Dolphin),
-];
\ No newline at end of file
+const PROCESS_NAMES: [(NamePattern, EmulatorType); 1] = [
+    (NamePattern::new("Dolphin{.exe,}"), EmulatorType::Dolphin),
+];
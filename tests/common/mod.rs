@@ -0,0 +1,92 @@
+//! Helpers for laying out fake emulator processes, so the signature scanning
+//! and pointer chains of every backend can be tested without a live runtime.
+#![allow(dead_code)]
+
+use asr::{Address, MemoryRangeFlags};
+use asr_emu_help::shared::{InMemoryProcess, MemoryBlock, Module};
+
+/// A fake emulator process, made of loaded modules and memory blocks.
+#[derive(Default)]
+pub struct Fixture {
+    modules: Vec<(String, u64, u64)>,
+    blocks: Vec<(u64, MemoryRangeFlags, Vec<u8>)>,
+}
+
+impl Fixture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps a read-only module image of `size` bytes at `address`.
+    pub fn module(mut self, name: &str, address: u64, size: usize) -> Self {
+        self.modules.push((name.to_owned(), address, size as u64));
+        self.block(address, size, MemoryRangeFlags::READ)
+    }
+
+    /// Maps a module image and marks it as a 64-bit PE.
+    pub fn module_64(self, name: &str, address: u64, size: usize) -> Self {
+        self.module(name, address, size).sig(address + 0x80, "50 45 00 00 64 86")
+    }
+
+    /// Maps a zeroed memory block of `size` bytes at `address`.
+    pub fn block(mut self, address: u64, size: usize, flags: MemoryRangeFlags) -> Self {
+        self.blocks.push((address, flags, vec![0; size]));
+        self
+    }
+
+    /// Maps a zeroed, writable memory block of `size` bytes at `address`.
+    pub fn data(self, address: u64, size: usize) -> Self {
+        self.block(address, size, MemoryRangeFlags::READ | MemoryRangeFlags::WRITE)
+    }
+
+    /// Writes raw bytes at the given address, which has to be inside a mapped block.
+    pub fn bytes(mut self, address: u64, bytes: &[u8]) -> Self {
+        let (base, _, data) = self.blocks.iter_mut()
+            .find(|(base, _, data)| address >= *base && address + bytes.len() as u64 <= *base + data.len() as u64)
+            .unwrap_or_else(|| panic!("address {address:#X} is not mapped"));
+        let offset = (address - *base) as usize;
+        data[offset..offset + bytes.len()].copy_from_slice(bytes);
+        self
+    }
+
+    /// Writes a signature at the given address, leaving its wildcards zeroed.
+    pub fn sig(self, address: u64, signature: &str) -> Self {
+        let bytes: Vec<u8> = signature
+            .split_whitespace()
+            .flat_map(|token| token.as_bytes().chunks(2).map(|b| match b {
+                b"??" => 0,
+                b => u8::from_str_radix(core::str::from_utf8(b).unwrap(), 16).unwrap(),
+            }).collect::<Vec<_>>())
+            .collect();
+        self.bytes(address, &bytes)
+    }
+
+    pub fn u8(self, address: u64, value: u8) -> Self {
+        self.bytes(address, &[value])
+    }
+
+    pub fn u32(self, address: u64, value: u32) -> Self {
+        self.bytes(address, &value.to_le_bytes())
+    }
+
+    pub fn u64(self, address: u64, value: u64) -> Self {
+        self.bytes(address, &value.to_le_bytes())
+    }
+
+    /// Writes the RIP-relative displacement found at `address`, so that it resolves to `target`.
+    pub fn rip(self, address: u64, target: u64) -> Self {
+        let displacement = target as i64 - (address as i64 + 4);
+        self.bytes(address, &(displacement as i32).to_le_bytes())
+    }
+
+    /// Runs `f` against an [`InMemoryProcess`] built from this fixture.
+    pub fn run<R>(&self, f: impl FnOnce(InMemoryProcess<'_>) -> R) -> R {
+        let modules: Vec<Module<'_>> = self.modules.iter()
+            .map(|(name, address, size)| Module { name, address: Address(*address), size: *size })
+            .collect();
+        let blocks: Vec<MemoryBlock<'_>> = self.blocks.iter()
+            .map(|(address, flags, data)| MemoryBlock { address: Address(*address), flags: *flags, data })
+            .collect();
+        f(InMemoryProcess { modules: &modules, blocks: &blocks })
+    }
+}
@@ -0,0 +1,67 @@
+#![cfg(all(feature = "derive", feature = "wii"))]
+
+mod common;
+
+use asr_emu_help::{wii::{Emulator, EmulatorType}, Endian, FromEndian};
+use bytemuck::{Pod, Zeroable};
+use common::Fixture;
+
+const MEM_1: u64 = 0x7FFF0000;
+const MEM_2: u64 = 0x8FFF0000;
+
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable, FromEndian)]
+#[repr(C)]
+#[emu(wii = 0x80001000)]
+struct Player {
+    lives: u32,
+    position: [i16; 2],
+    timers: [[u16; 2]; 2],
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable, FromEndian)]
+#[repr(C)]
+struct Pair(u16, u16);
+
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable, FromEndian)]
+#[repr(C, packed)]
+struct Packed {
+    flag: u8,
+    value: u32,
+    values: [u16; 2],
+}
+
+#[test]
+fn swaps_every_field() {
+    let pair = Pair(0x1122, 0x3344);
+    assert_eq!(pair.from_endian(Endian::Big), Pair(0x2211, 0x4433));
+    assert_eq!(pair.from_endian(Endian::Little), pair);
+}
+
+#[test]
+fn swaps_packed_fields() {
+    let packed = Packed { flag: 1, value: 0x11223344, values: [0x5566, 0x7788] };
+    assert_eq!(packed.from_endian(Endian::Big), Packed { flag: 1, value: 0x44332211, values: [0x6655, 0x8877] });
+}
+
+#[test]
+fn reads_bound_struct() {
+    let mut bytes = Vec::new();
+    bytes.extend(3u32.to_be_bytes());
+    bytes.extend((-2i16).to_be_bytes());
+    bytes.extend(5i16.to_be_bytes());
+    for timer in [1u16, 2, 3, 4] {
+        bytes.extend(timer.to_be_bytes());
+    }
+
+    let fixture = Fixture::new()
+        .data(MEM_1, 0x2000000)
+        .data(MEM_2, 0x4000000)
+        .bytes(MEM_1 + 0x1000, &bytes);
+
+    fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Dolphin);
+        assert!(emulator.update());
+        assert_eq!(Player::ADDRESS, 0x80001000);
+        assert_eq!(Player::read_from(&emulator), Ok(Player { lives: 3, position: [-2, 5], timers: [[1, 2], [3, 4]] }));
+    })
+}
@@ -0,0 +1,13 @@
+use asr_emu_help::{Error, ReadResult};
+
+#[test]
+fn transient_errors() {
+    assert_eq!(Ok::<u32, Error>(5).transient_as_none(), Ok(Some(5)));
+    assert_eq!(Err::<u32, Error>(Error::NotAttached).transient_as_none(), Ok(None));
+    assert_eq!(Err::<u32, Error>(Error::RamNotFound).transient_as_none(), Ok(None));
+    assert_eq!(Err::<u32, Error>(Error::NullPointer { address: 0x80010000 }).transient_as_none(), Ok(None));
+
+    let out_of_range = Error::OutOfRange { address: 0x200000, size: 4 };
+    assert_eq!(Err::<u32, Error>(out_of_range).transient_as_none(), Err(out_of_range));
+    assert!(!Error::ReadFailed { address: 0, size: 4 }.is_transient());
+}
@@ -0,0 +1,199 @@
+#![cfg(feature = "genesis")]
+
+mod common;
+
+use asr::{Address, primitives::dynamic_endian::Endian};
+use asr_emu_help::{genesis::{Emulator, EmulatorType}, Error};
+use common::Fixture;
+
+const MODULE: u64 = 0x400000;
+const CORE: u64 = 0x7000000;
+const DATA: u64 = 0x10000000;
+const WRAM: u64 = 0x20000000;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Endianness {
+    Big,
+    Little,
+}
+
+/// Maps the emulated RAM, storing the 68k words `0x1234 0x5678` at offset 0 with the given byte order.
+fn with_wram(fixture: Fixture, endianness: Endianness) -> Fixture {
+    let words: [u8; 4] = match endianness {
+        Endianness::Big => [0x12, 0x34, 0x56, 0x78],
+        Endianness::Little => [0x34, 0x12, 0x78, 0x56],
+    };
+    fixture.data(WRAM, 0x10000).bytes(WRAM, &words)
+}
+
+/// Hooks the emulator and checks both the RAM base and the endianness it resolved to,
+/// reading values as they would be seen on the original hardware.
+fn assert_hooks(fixture: Fixture, emulator_type: EmulatorType, endianness: Endianness) {
+    with_wram(fixture, endianness).run(|process| {
+        let mut emulator = Emulator::new(process, emulator_type);
+        assert!(emulator.update(), "{emulator_type:?} did not hook");
+        assert_eq!(emulator.read::<u16>(0), Ok(0x1234));
+        assert_eq!(emulator.read::<u8>(0), Ok(0x12));
+        assert_eq!(emulator.read::<u8>(1), Ok(0x34));
+        assert_eq!(emulator.read::<u32>(0), Ok(0x12345678), "{emulator_type:?} {endianness:?}");
+        assert_eq!(emulator.read::<u16>(1), Ok(0x3456), "{emulator_type:?} {endianness:?}");
+        assert_eq!(emulator.read_array::<u8, 3>(1), Ok([0x34, 0x56, 0x78]), "{emulator_type:?} {endianness:?}");
+        assert_eq!(emulator.read::<u64>(0xFFFC), Err(Error::OutOfRange { address: 0xFFFC, size: 8 }));
+    })
+}
+
+#[test]
+fn fusion() {
+    let fixture = Fixture::new()
+        .module("Fusion.exe", MODULE, 0x1000)
+        .data(DATA, 0x100)
+        .sig(MODULE + 0x100, "75 2F 6A 01")
+        .u32(MODULE + 0x133, DATA as u32)
+        .u32(DATA, WRAM as u32);
+
+    assert_hooks(fixture, EmulatorType::Fusion, Endianness::Big);
+}
+
+#[test]
+fn gens() {
+    for (probe, endianness) in [(0x86, Endianness::Big), (0x00, Endianness::Little)] {
+        let fixture = Fixture::new()
+            .module("gens.exe", MODULE, 0x1000)
+            .sig(MODULE + 0x100, "72 ?? 81 ?? FF FF 00 00 66 8B")
+            .u32(MODULE + 0x10B, WRAM as u32)
+            .u8(MODULE + 0x10F, probe);
+
+        assert_hooks(fixture, EmulatorType::Gens, endianness);
+    }
+}
+
+#[test]
+fn blastem() {
+    let fixture = Fixture::new()
+        .module("blastem.exe", MODULE, 0x1000)
+        .data(DATA, 0x101000)
+        .sig(DATA + 0x8000, "72 0E 81 E1 FF FF 00 00 66 8B 89 ?? ?? ?? ?? C3")
+        .u32(DATA + 0x800B, WRAM as u32);
+
+    assert_hooks(fixture, EmulatorType::BlastEm, Endianness::Little);
+}
+
+#[test]
+fn sega_game_room() {
+    let fixture = Fixture::new()
+        .module("SEGAGameRoom.exe", MODULE, 0x1000)
+        .module("GenesisEmuWrapper.dll", CORE, 0x1000)
+        .data(DATA, 0x100)
+        .sig(CORE + 0x100, "C7 05 ???????? ???????? A3 ???????? A3")
+        .u32(CORE + 0x102, DATA as u32)
+        .u32(DATA, WRAM as u32);
+
+    assert_hooks(fixture, EmulatorType::SegaClassics, Endianness::Little);
+}
+
+#[test]
+fn sega_genesis_classics() {
+    let fixture = Fixture::new()
+        .module("SEGAGenesisClassics.exe", MODULE, 0x1000)
+        .data(DATA, 0x100)
+        .sig(MODULE + 0x100, "89 2D ???????? 89 0D")
+        .u32(MODULE + 0x108, DATA as u32)
+        .u32(DATA, WRAM as u32);
+
+    assert_hooks(fixture, EmulatorType::SegaClassics, Endianness::Little);
+}
+
+#[test]
+fn retroarch() {
+    let retroarch = |is_64_bit: bool, core: &str| {
+        let fixture = Fixture::new();
+        let fixture = if is_64_bit {
+            fixture.module_64("retroarch.exe", MODULE, 0x1000)
+        } else {
+            fixture.module("retroarch.exe", MODULE, 0x1000)
+        };
+        fixture.module(core, CORE, 0x2000)
+    };
+
+    let cores = [
+        // BlastEm
+        retroarch(true, "blastem_libretro.dll")
+            .data(DATA, 0x101000)
+            .sig(DATA + 0x8000, "72 0E 81 E1 FF FF 00 00 66 8B 89 ?? ?? ?? ?? C3")
+            .u32(DATA + 0x800B, WRAM as u32),
+        // Genesis Plus GX
+        retroarch(true, "genesis_plus_gx_libretro.dll")
+            .sig(CORE + 0x100, "48 8D 0D ?? ?? ?? ?? 4C 8B 2D")
+            .rip(CORE + 0x103, WRAM),
+        retroarch(false, "genesis_plus_gx_wide_libretro.dll")
+            .sig(CORE + 0x100, "A3 ?? ?? ?? ?? 29 F9")
+            .u32(CORE + 0x101, WRAM as u32),
+        // Picodrive
+        retroarch(true, "picodrive_libretro.dll")
+            .sig(CORE + 0x100, "48 8D 0D ?? ?? ?? ?? 41 B8")
+            .rip(CORE + 0x103, WRAM),
+        retroarch(false, "picodrive_libretro.dll")
+            .sig(CORE + 0x100, "B9 ?? ?? ?? ?? C1 EF 10")
+            .u32(CORE + 0x101, WRAM as u32),
+    ];
+
+    for fixture in cores {
+        assert_hooks(fixture, EmulatorType::Retroarch, Endianness::Little);
+    }
+}
+
+#[test]
+fn slices() {
+    for (probe, endianness) in [(0x86, Endianness::Big), (0x00, Endianness::Little)] {
+        let mut guest: [u8; 16] = core::array::from_fn(|i| i as u8);
+        if endianness == Endianness::Little {
+            guest.chunks_exact_mut(2).for_each(|word| word.swap(0, 1));
+        }
+
+        let fixture = Fixture::new()
+            .module("gens.exe", MODULE, 0x1000)
+            .sig(MODULE + 0x100, "72 ?? 81 ?? FF FF 00 00 66 8B")
+            .u32(MODULE + 0x10B, WRAM as u32)
+            .u8(MODULE + 0x10F, probe)
+            .data(WRAM, 0x10000)
+            .bytes(WRAM, &guest);
+
+        fixture.run(|process| {
+            let mut emulator = Emulator::new(process, EmulatorType::Gens);
+            assert!(emulator.update());
+            assert_eq!(emulator.read_array::<u8, 5>(1), Ok([1, 2, 3, 4, 5]), "{endianness:?}");
+            assert_eq!(emulator.read_array::<u16, 2>(0xFF0002), Ok([0x0203, 0x0405]), "{endianness:?}");
+            assert_eq!(emulator.read_array::<u32, 2>(4), Ok([0x04050607, 0x08090A0B]), "{endianness:?}");
+            assert!(emulator.read_array::<u8, 2>(0xFFFF).is_err());
+        })
+    }
+}
+
+#[test]
+fn strings() {
+    for (probe, endianness) in [(0x86, Endianness::Big), (0x00, Endianness::Little)] {
+        let mut guest = *b"xSONIC\0\0";
+        if endianness == Endianness::Little {
+            guest.chunks_exact_mut(2).for_each(|word| word.swap(0, 1));
+        }
+
+        let fixture = Fixture::new()
+            .module("gens.exe", MODULE, 0x1000)
+            .sig(MODULE + 0x100, "72 ?? 81 ?? FF FF 00 00 66 8B")
+            .u32(MODULE + 0x10B, WRAM as u32)
+            .u8(MODULE + 0x10F, probe)
+            .data(WRAM, 0x10000)
+            .bytes(WRAM, &guest);
+
+        fixture.run(|process| {
+            let mut emulator = Emulator::new(process, EmulatorType::Gens);
+            assert!(emulator.update());
+            assert!(emulator.read_string::<7>(1).unwrap().matches("SONIC"), "{endianness:?}");
+            assert!(emulator.read_string::<3>(1).unwrap().matches("SON"), "{endianness:?}");
+
+            let domain = emulator.memory_domains().next().unwrap();
+            assert_eq!((domain.guest_address, domain.size, domain.host_address), (0xFF0000, 0x10000, Address(WRAM)));
+            assert_eq!(domain.endian == Endian::Little, endianness == Endianness::Little);
+        })
+    }
+}
@@ -0,0 +1,41 @@
+use asr_emu_help::shared::NamePattern;
+
+#[test]
+fn alternatives() {
+    let pattern = NamePattern::new("duckstation-{qt,nogui}{.exe,}");
+    let names = ["duckstation-qt.exe", "duckstation-nogui.exe", "duckstation-qt", "duckstation-nogui"];
+
+    assert!(pattern.names().take(4).map(|name| name.to_string()).eq(names));
+    assert!(pattern.matches("DuckStation-Qt.exe"));
+    assert!(!pattern.matches("duckstation.exe"));
+}
+
+#[test]
+fn casing() {
+    let pattern = NamePattern::new("XEBRA{.EXE,}");
+    let names: Vec<_> = pattern.names().map(|name| name.to_string()).collect();
+
+    assert_eq!(names, ["XEBRA.EXE", "XEBRA", "xebra.exe", "xebra"]);
+
+    // Lowercase names are only tried once
+    let pattern = NamePattern::new("retroarch{.exe,}");
+    assert_eq!(pattern.names().count(), 2);
+}
+
+#[test]
+fn truncation() {
+    let pattern = NamePattern::new("SEGAGenesisClassics.exe");
+    let name = pattern.names().next().unwrap();
+
+    assert_eq!(&*name.truncated(15), "SEGAGenesisClas");
+    assert_eq!(&*name.truncated(64), "SEGAGenesisClassics.exe");
+}
+
+#[test]
+fn case_insensitive_matching() {
+    let pattern = NamePattern::new("duckstation-{qt,nogui}-x64-ReleaseLTCG{.exe,}");
+
+    assert!(pattern.matches("Duckstation-Qt-x64-releaseltcg.EXE"));
+    assert!(pattern.matches("DUCKSTATION-NOGUI-X64-RELEASELTCG"));
+    assert!(!pattern.matches("duckstation-qt-x64-ReleaseLTCG.exe.bak"));
+}
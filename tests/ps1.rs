@@ -0,0 +1,775 @@
+#![cfg(feature = "ps1")]
+
+mod common;
+
+use core::cell::Cell;
+use asr::{Address, signature::Signature};
+use asr_emu_help::{ps1::{Emulator, EmulatorType, Event, Region, RetroarchCore}, shared::{AttachStatus, InMemoryProcess, MemoryRegion, MemorySource, Selection}, Error};
+use bytemuck::CheckedBitPattern;
+use common::Fixture;
+
+const MODULE: u64 = 0x400000;
+const DATA: u64 = 0x10000000;
+const WRAM: u64 = 0x20000000;
+
+fn resolve(fixture: &Fixture, emulator_type: EmulatorType) -> Option<Address> {
+    fixture.run(|process| {
+        let mut emulator = Emulator::new(process, emulator_type);
+        emulator.update();
+        emulator.ram().map(|ram| ram.base())
+    })
+}
+
+#[test]
+fn epsxe() {
+    let fixture = Fixture::new()
+        .module("ePSXe.exe", MODULE, 0x1000)
+        .sig(MODULE + 0x100, "C1 E1 10 8D 89")
+        .u32(MODULE + 0x105, WRAM as u32);
+
+    assert_eq!(resolve(&fixture, EmulatorType::Epsxe), Some(Address(WRAM)));
+}
+
+#[test]
+fn psxfin() {
+    let signatures = [
+        ("8B 15 ?? ?? ?? ?? 8D 34 1A", 2), // v1.13
+        ("A1 ?? ?? ?? ?? 8D 34 18", 1), // v1.12
+        ("A1 ?? ?? ?? ?? 8B 7C 24 14", 1), // v1.5 through v1.11
+        ("A1 ?? ?? ?? ?? 8B 6C 24", 1), // v1.0 through v1.4
+    ];
+
+    for (signature, offset) in signatures {
+        let fixture = Fixture::new()
+            .module("psxfin.exe", MODULE, 0x1000)
+            .data(DATA, 0x100)
+            .sig(MODULE + 0x100, signature)
+            .u32(MODULE + 0x100 + offset, DATA as u32)
+            .u32(DATA, WRAM as u32);
+
+        assert_eq!(resolve(&fixture, EmulatorType::PsxFin), Some(Address(WRAM)), "{signature}");
+    }
+
+    let null_ram = Fixture::new()
+        .module("psxfin.exe", MODULE, 0x1000)
+        .data(DATA, 0x100)
+        .sig(MODULE + 0x100, signatures[0].0)
+        .u32(MODULE + 0x102, DATA as u32);
+
+    assert_eq!(resolve(&null_ram, EmulatorType::PsxFin), None);
+}
+
+#[test]
+fn duckstation() {
+    let fixture = Fixture::new()
+        .module("duckstation-qt-x64-ReleaseLTCG.exe", MODULE, 0x2000)
+        .sig(MODULE + 0x100, "48 89 0D ?? ?? ?? ?? B8")
+        .rip(MODULE + 0x103, MODULE + 0x1800)
+        .u64(MODULE + 0x1800, WRAM);
+
+    assert_eq!(resolve(&fixture, EmulatorType::Duckstation), Some(Address(WRAM)));
+}
+
+#[test]
+fn pcsx_redux_64_bit() {
+    let fixture = Fixture::new()
+        .module_64("pcsx-redux.main", MODULE, 0x2000)
+        .data(DATA, 0x100)
+        .sig(MODULE + 0x200, "48 B9 ?? ?? ?? ?? ?? ?? ?? ?? E8 ?? ?? ?? ?? C7 85 ?? ?? ?? ?? 00 00 00 00")
+        .u64(MODULE + 0x202, DATA)
+        .sig(MODULE + 0x300, "89 D1 C1 E9 10 48 8B ?? 40")
+        .u64(DATA + 0x40, DATA + 0x80)
+        .u64(DATA + 0x80, WRAM);
+
+    assert_eq!(resolve(&fixture, EmulatorType::PcsxRedux), Some(Address(WRAM)));
+}
+
+#[test]
+fn pcsx_redux_32_bit() {
+    // The instruction reads the RAM pointer directly, so its low byte is part of the signature
+    const WRAM_32: u32 = 0x00600020;
+
+    let fixture = Fixture::new()
+        .module("pcsx-redux.main", MODULE, 0x1000)
+        .data(DATA, 0x1000)
+        .sig(DATA + 0x400, "8B 3D 20 ?? ?? ?? 0F B7 D3 8B 04 95 ?? ?? ?? ?? 21 05")
+        .u32(DATA + 0x402, WRAM_32);
+
+    assert_eq!(resolve(&fixture, EmulatorType::PcsxRedux), Some(Address(WRAM_32 as u64)));
+}
+
+#[test]
+fn xebra() {
+    let fixture = Fixture::new()
+        .module("XEBRA.EXE", MODULE, 0x1000)
+        .data(DATA, 0x1000)
+        .sig(MODULE + 0x100, "E8 ?? ?? ?? ?? E9 ?? ?? ?? ?? 89 C8 C1 F8 10")
+        .rip(MODULE + 0x101, MODULE + 0x800)
+        .u32(MODULE + 0x800 + 0x16A, DATA as u32)
+        .u32(DATA, WRAM as u32);
+
+    assert_eq!(resolve(&fixture, EmulatorType::Xebra), Some(Address(WRAM)));
+}
+
+#[test]
+fn retroarch() {
+    const CORE: u64 = 0x7000000;
+
+    let retroarch = |is_64_bit: bool, core: &str| {
+        let fixture = Fixture::new();
+        let fixture = if is_64_bit {
+            fixture.module_64("retroarch.exe", MODULE, 0x1000)
+        } else {
+            fixture.module("retroarch.exe", MODULE, 0x1000)
+        };
+        fixture.module(core, CORE, 0x2000).data(DATA, 0x100)
+    };
+
+    let cores = [
+        // Beetle PSX
+        retroarch(true, "mednafen_psx_hw_libretro.dll")
+            .sig(CORE + 0x100, "48 8B 05 ?? ?? ?? ?? 41 81 E4 FF FF 1F 00")
+            .rip(CORE + 0x103, CORE + 0x1800)
+            .u64(CORE + 0x1800, WRAM),
+        retroarch(false, "mednafen_psx_libretro.dll")
+            .sig(CORE + 0x100, "A1 ?? ?? ?? ?? 81 E3 FF FF 1F 00")
+            .u32(CORE + 0x101, DATA as u32)
+            .u32(DATA, WRAM as u32),
+        // SwanStation
+        retroarch(true, "swanstation_libretro.dll")
+            .sig(CORE + 0x100, "48 89 0D ?? ?? ?? ?? 89 35 ?? ?? ?? ?? 89 3D")
+            .rip(CORE + 0x103, CORE + 0x1800)
+            .u64(CORE + 0x1800, WRAM),
+        retroarch(false, "swanstation_libretro.dll")
+            .sig(CORE + 0x100, "A1 ?? ?? ?? ?? 23 CB 8B")
+            .u32(CORE + 0x101, DATA as u32)
+            .u32(DATA, WRAM as u32),
+        // PCSX ReARMed
+        retroarch(true, "pcsx_rearmed_libretro.dll")
+            .sig(CORE + 0x100, "48 8B 35 ?? ?? ?? ?? 81 E2")
+            .rip(CORE + 0x103, CORE + 0x1800)
+            .u64(CORE + 0x1800, DATA)
+            .u64(DATA, WRAM),
+        retroarch(false, "pcsx_rearmed_libretro.dll")
+            .sig(CORE + 0x100, "FF FF 1F 00 89 ?? ?? ?? A1")
+            .u32(CORE + 0x109, DATA as u32)
+            .u32(DATA, WRAM as u32),
+    ];
+
+    for (i, fixture) in cores.iter().enumerate() {
+        assert_eq!(resolve(fixture, EmulatorType::Retroarch), Some(Address(WRAM)), "core #{i}");
+    }
+}
+
+#[test]
+fn missing_signature() {
+    let fixture = Fixture::new().module("duckstation-qt-x64-ReleaseLTCG.exe", MODULE, 0x1000);
+    assert_eq!(resolve(&fixture, EmulatorType::Duckstation), None);
+}
+
+fn status(fixture: &Fixture, emulator_type: EmulatorType) -> AttachStatus<EmulatorType> {
+    fixture.run(|process| {
+        let mut emulator = Emulator::new(process, emulator_type);
+        emulator.update();
+        emulator.status()
+    })
+}
+
+#[test]
+fn attach_status() {
+    let hooked = Fixture::new()
+        .module("ePSXe.exe", MODULE, 0x1000)
+        .sig(MODULE + 0x100, "C1 E1 10 8D 89")
+        .u32(MODULE + 0x105, WRAM as u32);
+    assert_eq!(status(&hooked, EmulatorType::Epsxe), AttachStatus::Hooked(EmulatorType::Epsxe));
+
+    let no_signature = Fixture::new().module("duckstation-qt-x64-ReleaseLTCG.exe", MODULE, 0x1000);
+    let result = status(&no_signature, EmulatorType::Duckstation);
+    assert_eq!(result, AttachStatus::RamNotFound(EmulatorType::Duckstation));
+    assert!(result.rescan_pending());
+
+    let no_core = Fixture::new().module_64("retroarch.exe", MODULE, 0x1000);
+    let result = status(&no_core, EmulatorType::Retroarch);
+    assert_eq!(result, AttachStatus::UnsupportedCore(EmulatorType::Retroarch));
+    assert_eq!(result.emulator(), Some(EmulatorType::Retroarch));
+}
+
+/// Counts the module lookups performed against the wrapped process.
+struct CountingProcess<'a> {
+    process: InMemoryProcess<'a>,
+    module_lookups: &'a Cell<u32>,
+    signature_scans: &'a Cell<u32>,
+}
+
+impl MemorySource for CountingProcess<'_> {
+    fn is_open(&self) -> bool {
+        self.process.is_open()
+    }
+
+    fn module_address(&self, name: &str) -> Option<Address> {
+        self.module_lookups.set(self.module_lookups.get() + 1);
+        self.process.module_address(name)
+    }
+
+    fn module_size(&self, name: &str) -> Option<u64> {
+        self.process.module_size(name)
+    }
+
+    fn memory_ranges(&self) -> impl Iterator<Item = MemoryRegion> + '_ {
+        self.process.memory_ranges()
+    }
+
+    fn read<T: CheckedBitPattern>(&self, address: Address) -> Option<T> {
+        self.process.read(address)
+    }
+
+    fn read_into_buf(&self, address: Address, buf: &mut [u8]) -> Option<()> {
+        self.process.read_into_buf(address, buf)
+    }
+
+    fn scan_signature<const N: usize>(&self, signature: &Signature<N>, address: Address, len: u64) -> Option<Address> {
+        self.signature_scans.set(self.signature_scans.get() + 1);
+        self.process.scan_signature(signature, address, len)
+    }
+}
+
+#[test]
+fn skipped_rescans_dont_look_up_modules() {
+    let fixture = Fixture::new().module("duckstation-qt-x64-ReleaseLTCG.exe", MODULE, 0x1000);
+
+    fixture.run(|process| {
+        let (module_lookups, signature_scans) = (Cell::new(0), Cell::new(0));
+        let process = CountingProcess { process, module_lookups: &module_lookups, signature_scans: &signature_scans };
+        let mut emulator = Emulator::new(process, EmulatorType::Duckstation);
+
+        // With the default policy, lookups happen on the 1st, 3rd and 6th updates
+        let scans: Vec<bool> = (0..8).map(|_| {
+            let lookups = module_lookups.get();
+            emulator.update();
+            module_lookups.get() != lookups
+        }).collect();
+
+        assert_eq!(scans, [true, false, true, false, false, true, false, false]);
+    })
+}
+
+#[test]
+fn renamed_builds() {
+    for name in ["duckstation-qt", "duckstation-nogui.AppImage", "duckstation-qt-x64-releaseltcg.exe"] {
+        let fixture = Fixture::new()
+            .module(name, MODULE, 0x1000)
+            .sig(MODULE + 0x100, "48 89 0D ?? ?? ?? ?? B8")
+            .rip(MODULE + 0x103, MODULE + 0x800)
+            .u64(MODULE + 0x800, WRAM);
+
+        assert_eq!(resolve(&fixture, EmulatorType::Duckstation), Some(Address(WRAM)), "{name}");
+    }
+}
+
+#[test]
+fn retroarch_core_selection() {
+    let fixture = Fixture::new()
+        .module_64("retroarch.exe", MODULE, 0x1000)
+        .module("swanstation_libretro.dll", 0x7000000, 0x2000)
+        .sig(0x7000100, "48 89 0D ?? ?? ?? ?? 89 35 ?? ?? ?? ?? 89 3D")
+        .rip(0x7000103, 0x7001800)
+        .u64(0x7001800, WRAM);
+
+    let hook = |cores: Selection<RetroarchCore>| fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Retroarch);
+        emulator.set_retroarch_cores(cores);
+        emulator.update();
+        (emulator.status(), emulator.retroarch_core())
+    });
+
+    assert_eq!(hook(Selection::only(&[RetroarchCore::SwanStation])), (AttachStatus::Hooked(EmulatorType::Retroarch), Some(RetroarchCore::SwanStation)));
+    assert_eq!(hook(Selection::any().deny(RetroarchCore::SwanStation)), (AttachStatus::UnsupportedCore(EmulatorType::Retroarch), None));
+}
+
+#[test]
+fn pointer_paths() {
+    let fixture = Fixture::new()
+        .module("ePSXe.exe", MODULE, 0x1000)
+        .sig(MODULE + 0x100, "C1 E1 10 8D 89")
+        .u32(MODULE + 0x105, WRAM as u32)
+        .data(WRAM, 0x200000)
+        .u32(WRAM + 0x100, 0x80001000)
+        .u32(WRAM + 0x1010, 0x2000) // Pointers can also be plain offsets
+        .u32(WRAM + 0x2004, 0xDEADBEEF)
+        .u32(WRAM + 0x104, 0x80300000);
+
+    fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Epsxe);
+        assert!(emulator.update());
+        assert_eq!(emulator.read_pointer_path::<u32>(0x80000100, &[0, 0x10, 4]), Ok(0xDEADBEEF));
+        assert_eq!(emulator.resolve_pointer_path(0x80000100, &[0, 0x10, 4]), Ok(0x2004));
+        assert_eq!(emulator.read_pointer_path::<u32>(0x80000100, &[0x10, 0]), Err(Error::NullPointer { address: 0x80000110 }));
+        assert_eq!(emulator.read_pointer_path::<u32>(0x80000104, &[0, 0]), Err(Error::OutOfRange { address: 0x80300000, size: 4 }));
+    })
+}
+
+#[test]
+fn snapshot() {
+    let fixture = Fixture::new()
+        .module("ePSXe.exe", MODULE, 0x1000)
+        .sig(MODULE + 0x100, "C1 E1 10 8D 89")
+        .u32(MODULE + 0x105, WRAM as u32)
+        .data(WRAM, 0x200000)
+        .u32(WRAM + 0x100, 0x11223344)
+        .u32(WRAM + 0x1000, 0x55667788)
+        .u32(WRAM + 0x2000, 0x99AABBCC);
+
+    fixture.run(|process| {
+        let buf: &'static mut [u8] = Vec::leak(vec![0; 0x20]);
+        let mut emulator = Emulator::new(process, EmulatorType::Epsxe);
+        // The second range doesn't fit in the buffer, so it's read live, while the third one still fits
+        emulator.enable_snapshot(buf, &[(0x80000100, 0x10), (0x1000, 0x20), (0x2000, 0x8)]);
+        assert!(emulator.update());
+
+        assert_eq!(emulator.read::<u32>(0x100), Ok(0x11223344));
+        assert_eq!(emulator.read::<u16>(0x80000102), Ok(0x1122));
+        assert_eq!(emulator.read_array::<u8, 0x14>(0x100).map(|bytes| bytes[..4] == 0x11223344u32.to_le_bytes()), Ok(true));
+        assert_eq!(emulator.read::<u32>(0x1000), Ok(0x55667788));
+        assert!(emulator.refresh_snapshot());
+
+        let buf = emulator.disable_snapshot().unwrap();
+        assert_eq!(buf[..4], 0x11223344u32.to_le_bytes());
+        assert_eq!(buf[0x10..0x14], 0x99AABBCCu32.to_le_bytes());
+        assert!(buf[0x18..].iter().all(|&b| b == 0));
+        assert!(!emulator.refresh_snapshot());
+    })
+}
+
+#[test]
+fn scratchpad_and_bios() {
+    const SCRATCHPAD: u64 = 0x30000000;
+    const BIOS: u64 = 0x40000000;
+
+    let fixture = Fixture::new()
+        .module_64("pcsx-redux.main", MODULE, 0x2000)
+        .data(DATA, 0x100)
+        .sig(MODULE + 0x200, "48 B9 ?? ?? ?? ?? ?? ?? ?? ?? E8 ?? ?? ?? ?? C7 85 ?? ?? ?? ?? 00 00 00 00")
+        .u64(MODULE + 0x202, DATA)
+        .sig(MODULE + 0x300, "89 D1 C1 E9 10 48 8B ?? 40")
+        .u64(DATA + 0x40, DATA + 0x80)
+        .u64(DATA + 0x80, WRAM)
+        .u64(DATA + 0x98, SCRATCHPAD)
+        .u64(DATA + 0x90, BIOS)
+        .data(BIOS, 0x80000)
+        .bytes(BIOS + 0x108, b"CEX-3000")
+        .data(SCRATCHPAD, 0x10000)
+        .u32(SCRATCHPAD + 0x3FC, 0x11223344);
+
+    fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::PcsxRedux);
+        assert!(emulator.update());
+
+        assert_eq!(emulator.ram().and_then(|ram| ram.scratchpad()), Some(Address(SCRATCHPAD)));
+        assert_eq!(emulator.read::<u32>(0x1F8003FC), Ok(0x11223344));
+        assert_eq!(emulator.read::<u32>(0x9F8003FC), Ok(0x11223344));
+        assert_eq!(emulator.read::<u32>(0x1F8003FE), Err(Error::OutOfRange { address: 0x1F8003FE, size: 4 }));
+        assert_eq!(emulator.read::<u32>(0xBF8003FC), Err(Error::OutOfRange { address: 0xBF8003FC, size: 4 }));
+        assert_eq!(emulator.memory_domains().nth(1).map(|domain| (domain.name, domain.host_address)), Some(("Scratchpad", Address(SCRATCHPAD))));
+
+        assert_eq!(emulator.read_bios::<[u8; 8]>(0x108), Ok(*b"CEX-3000"));
+        assert_eq!(emulator.read::<[u8; 8]>(0xBFC00108), Ok(*b"CEX-3000"));
+        assert_eq!(emulator.read::<[u8; 8]>(0x9FC00108), Ok(*b"CEX-3000"));
+        assert_eq!(emulator.read_bios::<u32>(0x7FFFE), Err(Error::OutOfRange { address: 0x7FFFE, size: 4 }));
+        assert_eq!(emulator.memory_domains().nth(2).map(|domain| (domain.name, domain.guest_address)), Some(("BIOS", 0xBFC00000)));
+    });
+
+    // Without them, both are unavailable
+    let fixture = Fixture::new()
+        .module("ePSXe.exe", MODULE, 0x1000)
+        .sig(MODULE + 0x100, "C1 E1 10 8D 89")
+        .u32(MODULE + 0x105, WRAM as u32)
+        .data(WRAM, 0x200000);
+
+    fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Epsxe);
+        assert!(emulator.update());
+
+        assert_eq!(emulator.read::<u8>(0x1F800000), Err(Error::Unavailable { address: 0x1F800000, size: 1 }));
+        assert_eq!(emulator.read_bios::<u8>(0), Err(Error::Unavailable { address: 0xBFC00000, size: 1 }));
+        assert_eq!(emulator.memory_domains().count(), 1);
+    })
+}
+
+#[test]
+fn scratchpad_pointers() {
+    const CORE: u64 = 0x7000000;
+    const HARDWARE: u64 = 0x30000000;
+
+    // The hardware registers start with the scratchpad, and the BIOS sets up the memory control registers
+    let hardware = |fixture: Fixture| fixture
+        .data(HARDWARE, 0x10000)
+        .u32(HARDWARE + 0x3FC, 0x11223344)
+        .u32(HARDWARE + 0x1000, 0x1F000000)
+        .u32(HARDWARE + 0x1004, 0x1F802000)
+        .u32(HARDWARE + 0x1010, 0x0013243F);
+
+    // PCSX ReARMed declares the pointer to them, psxH, after the one to the RAM, psxM
+    let rearmed_64 = |psx_h: u64| hardware(Fixture::new()
+        .module_64("retroarch.exe", MODULE, 0x1000)
+        .module("pcsx_rearmed_libretro.dll", CORE, 0x2000)
+        .data(DATA, 0x1000)
+        .sig(CORE + 0x100, "48 8B 35 ?? ?? ?? ?? 81 E2")
+        .rip(CORE + 0x103, CORE + 0x1800)
+        .u64(CORE + 0x1800, DATA)
+        .u64(DATA, WRAM)
+        .u64(psx_h, HARDWARE));
+    let rearmed_32 = hardware(Fixture::new()
+        .module("retroarch.exe", MODULE, 0x1000)
+        .module("pcsx_rearmed_libretro.dll", CORE, 0x2000)
+        .data(DATA, 0x100)
+        .sig(CORE + 0x100, "FF FF 1F 00 89 ?? ?? ?? A1")
+        .u32(CORE + 0x109, DATA as u32)
+        .u32(DATA, WRAM as u32)
+        .u32(DATA + 0xC, HARDWARE as u32));
+
+    for fixture in [rearmed_64(DATA + 0x18), rearmed_32] {
+        fixture.run(|process| {
+            let mut emulator = Emulator::new(process, EmulatorType::Retroarch);
+            assert!(emulator.update());
+
+            assert_eq!(emulator.ram().and_then(|ram| ram.scratchpad()), Some(Address(HARDWARE)));
+            assert_eq!(emulator.read::<u32>(0x1F8003FC), Ok(0x11223344));
+        });
+    }
+
+    // Only the pointers close to the anchor are checked, and DuckStation keeps the scratchpad inside its CPU state
+    let duckstation = hardware(Fixture::new()
+        .module("duckstation-qt-x64-ReleaseLTCG.exe", MODULE, 0x2000)
+        .sig(MODULE + 0x100, "48 89 0D ?? ?? ?? ?? B8")
+        .rip(MODULE + 0x103, MODULE + 0x1800)
+        .u64(MODULE + 0x1800, WRAM)
+        .u64(MODULE + 0x1808, HARDWARE));
+
+    for (emulator_type, fixture) in [(EmulatorType::Retroarch, rearmed_64(DATA + 0x800)), (EmulatorType::Duckstation, duckstation)] {
+        fixture.run(|process| {
+            let mut emulator = Emulator::new(process, emulator_type);
+            assert!(emulator.update());
+            assert_eq!(emulator.ram().and_then(|ram| ram.scratchpad()), None, "{emulator_type:?}");
+        });
+    }
+}
+
+#[test]
+fn scratchpad_near_ram() {
+    const HARDWARE: u64 = WRAM + 0x400000;
+
+    let epsxe = |hardware: u64, unused: u32| Fixture::new()
+        .module("ePSXe.exe", MODULE, 0x1000)
+        .sig(MODULE + 0x100, "C1 E1 10 8D 89")
+        .u32(MODULE + 0x105, WRAM as u32)
+        .data(WRAM, 0x200000)
+        .data(hardware, 0x10000)
+        .u32(hardware + 0x3FC, 0x11223344)
+        .u32(hardware + 0x800, unused)
+        .u32(hardware + 0x1000, 0x1F000000)
+        .u32(hardware + 0x1004, 0x1F802000)
+        .u32(hardware + 0x1010, 0x0013243F);
+
+    let read = |fixture: Fixture| fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Epsxe);
+        assert!(emulator.update());
+        emulator.read::<u32>(0x1F8003FC)
+    });
+
+    assert_eq!(read(epsxe(HARDWARE, 0)), Ok(0x11223344));
+    // The area between the scratchpad and the memory control registers is unused, so anything else is rejected
+    assert_eq!(read(epsxe(HARDWARE, 1)), Err(Error::Unavailable { address: 0x1F8003FC, size: 4 }));
+    // Memory far from the RAM isn't scanned
+    assert_eq!(read(epsxe(0x40000000, 0)), Err(Error::Unavailable { address: 0x1F8003FC, size: 4 }));
+
+    // The other emulators without pointers to the hardware registers are scanned the same way
+    const WRAM_32: u64 = 0x00600020;
+
+    let backends = [
+        (EmulatorType::PsxFin, HARDWARE, Fixture::new()
+            .module("psxfin.exe", MODULE, 0x1000)
+            .data(DATA, 0x100)
+            .sig(MODULE + 0x100, "8B 15 ?? ?? ?? ?? 8D 34 1A")
+            .u32(MODULE + 0x102, DATA as u32)
+            .u32(DATA, WRAM as u32)),
+        (EmulatorType::Xebra, HARDWARE, Fixture::new()
+            .module("XEBRA.EXE", MODULE, 0x1000)
+            .data(DATA, 0x1000)
+            .sig(MODULE + 0x100, "E8 ?? ?? ?? ?? E9 ?? ?? ?? ?? 89 C8 C1 F8 10")
+            .rip(MODULE + 0x101, MODULE + 0x800)
+            .u32(MODULE + 0x800 + 0x16A, DATA as u32)
+            .u32(DATA, WRAM as u32)),
+        (EmulatorType::PcsxRedux, 0x00800000, Fixture::new()
+            .module("pcsx-redux.main", MODULE, 0x1000)
+            .data(DATA, 0x1000)
+            .sig(DATA + 0x400, "8B 3D 20 ?? ?? ?? 0F B7 D3 8B 04 95 ?? ?? ?? ?? 21 05")
+            .u32(DATA + 0x402, WRAM_32 as u32)),
+    ];
+
+    for (emulator_type, hardware, fixture) in backends {
+        let fixture = fixture
+            .data(hardware, 0x10000)
+            .u32(hardware + 0x1000, 0x1F000000)
+            .u32(hardware + 0x1004, 0x1F802000)
+            .u32(hardware + 0x1010, 0x0013243F);
+
+        fixture.run(|process| {
+            let mut emulator = Emulator::new(process, emulator_type);
+            assert!(emulator.update());
+            assert_eq!(emulator.ram().and_then(|ram| ram.scratchpad()), Some(Address(hardware)), "{emulator_type:?}");
+        });
+    }
+}
+
+#[test]
+fn regions_arent_scanned_once_hooked() {
+    let fixture = Fixture::new()
+        .module("ePSXe.exe", MODULE, 0x1000)
+        .sig(MODULE + 0x100, "C1 E1 10 8D 89")
+        .u32(MODULE + 0x105, WRAM as u32)
+        .data(WRAM, 0x200000);
+
+    fixture.run(|process| {
+        let (module_lookups, signature_scans) = (Cell::new(0), Cell::new(0));
+        let process = CountingProcess { process, module_lookups: &module_lookups, signature_scans: &signature_scans };
+        let mut emulator = Emulator::new(process, EmulatorType::Epsxe);
+
+        assert!(emulator.update());
+        let scans = signature_scans.get();
+        for _ in 0..100 {
+            assert!(emulator.update());
+        }
+
+        assert_eq!(signature_scans.get(), scans);
+    })
+}
+
+#[test]
+fn bios_pointers() {
+    const CORE: u64 = 0x7000000;
+    const BIOS: u64 = 0x40000000;
+
+    // A 512KB image starting with the reset vector of Sony's BIOSes, along with its build date
+    let bios = |fixture: Fixture, address: u64| fixture
+        .sig(address, "13 00 08 3C 3F 24 08 35 80 1F 01 3C 10 10 28 AC")
+        .u32(address + 0x100, 0x19951204);
+
+    let duckstation = |size: usize| Fixture::new()
+        .module("duckstation-qt-x64-ReleaseLTCG.exe", MODULE, size)
+        .sig(MODULE + 0x100, "48 89 0D ?? ?? ?? ?? B8")
+        .rip(MODULE + 0x103, MODULE + 0x1800)
+        .u64(MODULE + 0x1800, WRAM)
+        .u32(MODULE + 0x1810, 0x200000)
+        .u32(MODULE + 0x1814, 0x1FFFFF);
+
+    let retroarch = |core: &str| Fixture::new()
+        .module_64("retroarch.exe", MODULE, 0x1000)
+        .module(core, CORE, 0x2000)
+        .data(DATA, 0x100)
+        .data(BIOS, 0x80000);
+
+    let backends = [
+        // DuckStation declares it after the size and mask of the RAM, as a pointer or, in older versions, as an array
+        ("DuckStation", EmulatorType::Duckstation, BIOS, bios(duckstation(0x2000).data(BIOS, 0x80000).u64(MODULE + 0x1818, BIOS), BIOS)),
+        ("DuckStation (array)", EmulatorType::Duckstation, MODULE + 0x1818, bios(duckstation(0x1818 + 0x80000), MODULE + 0x1818)),
+        // Beetle PSX declares BIOSROM before MainRAM
+        ("Beetle PSX", EmulatorType::Retroarch, BIOS, bios(retroarch("mednafen_psx_hw_libretro.dll")
+            .sig(CORE + 0x100, "48 8B 05 ?? ?? ?? ?? 41 81 E4 FF FF 1F 00")
+            .rip(CORE + 0x103, CORE + 0x1800)
+            .u64(CORE + 0x1800, WRAM)
+            .u64(CORE + 0x17E8, BIOS), BIOS)),
+        ("SwanStation", EmulatorType::Retroarch, BIOS, bios(retroarch("swanstation_libretro.dll")
+            .sig(CORE + 0x100, "48 89 0D ?? ?? ?? ?? 89 35 ?? ?? ?? ?? 89 3D")
+            .rip(CORE + 0x103, CORE + 0x1800)
+            .u64(CORE + 0x1800, WRAM)
+            .u64(CORE + 0x1818, BIOS), BIOS)),
+        // PCSX ReARMed declares psxR after psxM
+        ("PCSX ReARMed", EmulatorType::Retroarch, BIOS, bios(retroarch("pcsx_rearmed_libretro.dll")
+            .sig(CORE + 0x100, "48 8B 35 ?? ?? ?? ?? 81 E2")
+            .rip(CORE + 0x103, CORE + 0x1800)
+            .u64(CORE + 0x1800, DATA)
+            .u64(DATA, WRAM)
+            .u64(DATA + 0x10, BIOS), BIOS)),
+    ];
+
+    for (name, emulator_type, address, fixture) in backends {
+        fixture.run(|process| {
+            let mut emulator = Emulator::new(process, emulator_type);
+            assert!(emulator.update(), "{name}");
+
+            assert_eq!(emulator.ram().and_then(|ram| ram.bios()), Some(Address(address)), "{name}");
+            assert_eq!(emulator.read_bios::<u32>(0x100), Ok(0x19951204), "{name}");
+            assert_eq!(emulator.read::<u32>(0xBFC00100), Ok(0x19951204), "{name}");
+        });
+    }
+}
+
+#[test]
+fn bios_near_ram() {
+    const BIOS: u64 = WRAM + 0x400000;
+
+    let epsxe = |size: usize, date: u32| Fixture::new()
+        .module("ePSXe.exe", MODULE, 0x1000)
+        .sig(MODULE + 0x100, "C1 E1 10 8D 89")
+        .u32(MODULE + 0x105, WRAM as u32)
+        .data(WRAM, 0x200000)
+        .data(BIOS, size)
+        .sig(BIOS, "13 00 08 3C 3F 24 08 35 80 1F 01 3C 10 10 28 AC")
+        .u32(BIOS + 0x100, date);
+
+    let read = |fixture: Fixture| fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Epsxe);
+        assert!(emulator.update());
+        emulator.read_bios::<u32>(0x100)
+    });
+
+    assert_eq!(read(epsxe(0x80000, 0x19951204)), Ok(0x19951204));
+    // Code which only starts the same way, or a truncated image, isn't a BIOS
+    assert_eq!(read(epsxe(0x80000, 0x12345678)), Err(Error::Unavailable { address: 0xBFC00100, size: 4 }));
+    assert_eq!(read(epsxe(0x1000, 0x19951204)), Err(Error::Unavailable { address: 0xBFC00100, size: 4 }));
+}
+
+#[test]
+fn mirrors_and_dev_kit_ram() {
+    let duckstation = |ram_size: u32| Fixture::new()
+        .module("duckstation-qt-x64-ReleaseLTCG.exe", MODULE, 0x2000)
+        .sig(MODULE + 0x100, "48 89 0D ?? ?? ?? ?? B8")
+        .rip(MODULE + 0x103, MODULE + 0x1800)
+        .u64(MODULE + 0x1800, WRAM)
+        .u32(MODULE + 0x1810, ram_size)
+        .u32(MODULE + 0x1814, ram_size - 1)
+        .data(WRAM, 0x800000)
+        .u32(WRAM + 0x1234, 0x11223344)
+        .u32(WRAM + 0x7FFFFC, 0x55667788);
+
+    duckstation(0x200000).run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Duckstation);
+        assert!(emulator.update());
+
+        assert_eq!(emulator.ram().map(|ram| ram.size()), Some(0x200000));
+        assert_eq!(emulator.read::<u32>(0x1234), Ok(0x11223344));
+        assert_eq!(emulator.read::<u32>(0x80001234), Ok(0x11223344));
+        assert_eq!(emulator.read::<u32>(0xA0001234), Ok(0x11223344));
+        assert_eq!(emulator.read::<u32>(0xC0001234), Err(Error::OutOfRange { address: 0xC0001234, size: 4 }));
+        assert_eq!(emulator.read::<u32>(0xA01FFFFE), Err(Error::OutOfRange { address: 0xA01FFFFE, size: 4 }));
+        assert_eq!(emulator.read::<u32>(0x807FFFFC), Err(Error::OutOfRange { address: 0x807FFFFC, size: 4 }));
+    });
+
+    duckstation(0x800000).run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Duckstation);
+        assert!(emulator.update());
+
+        assert_eq!(emulator.ram().map(|ram| ram.size()), Some(0x800000));
+        assert_eq!(emulator.read::<u32>(0x807FFFFC), Ok(0x55667788));
+        assert_eq!(emulator.read::<u32>(0xA07FFFFC), Ok(0x55667788));
+        assert_eq!(emulator.memory_domains().next().map(|domain| domain.size), Some(0x800000));
+    });
+
+    // PCSX-Redux maps the page at 2MB to the third megabyte of the RAM instead of its start
+    const READ_LUT: u64 = DATA + 0x1000;
+
+    let pcsx_redux = |page_at_2mb: u64| Fixture::new()
+        .module_64("pcsx-redux.main", MODULE, 0x2000)
+        .data(DATA, 0x2000)
+        .sig(MODULE + 0x200, "48 B9 ?? ?? ?? ?? ?? ?? ?? ?? E8 ?? ?? ?? ?? C7 85 ?? ?? ?? ?? 00 00 00 00")
+        .u64(MODULE + 0x202, DATA)
+        .sig(MODULE + 0x300, "89 D1 C1 E9 10 48 8B ?? 40")
+        .u64(DATA + 0x40, DATA + 0x80)
+        .u64(DATA + 0x80, WRAM)
+        .u64(DATA + 0xA8, READ_LUT)
+        .u64(READ_LUT, WRAM)
+        .u64(READ_LUT + 0x20 * 8, page_at_2mb)
+        .data(WRAM, 0x800000)
+        .u32(WRAM + 0x7FFFFC, 0x55667788);
+
+    pcsx_redux(WRAM).run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::PcsxRedux);
+        assert!(emulator.update());
+
+        assert_eq!(emulator.ram().map(|ram| ram.size()), Some(0x200000));
+        assert_eq!(emulator.read::<u32>(0x807FFFFC), Err(Error::OutOfRange { address: 0x807FFFFC, size: 4 }));
+    });
+
+    pcsx_redux(WRAM + 0x200000).run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::PcsxRedux);
+        assert!(emulator.update());
+
+        assert_eq!(emulator.ram().map(|ram| ram.size()), Some(0x800000));
+        assert_eq!(emulator.read::<u32>(0x807FFFFC), Ok(0x55667788));
+    })
+}
+
+#[test]
+fn game_id() {
+    let epsxe = |path_address: u64, path: &[u8]| Fixture::new()
+        .module("ePSXe.exe", MODULE, 0x1000)
+        .sig(MODULE + 0x100, "C1 E1 10 8D 89")
+        .u32(MODULE + 0x105, WRAM as u32)
+        .data(WRAM, 0x200000)
+        .bytes(WRAM + path_address, path);
+
+    let game_id = |fixture: Fixture| fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Epsxe);
+        assert!(emulator.update());
+        emulator.game_id()
+    });
+
+    // The path crosses the boundary between two chunks
+    let id = game_id(epsxe(0x3F0, b"cdrom:\\SLUS_012.34;1\0")).unwrap();
+    assert_eq!((id.serial(), id.region()), ("SLUS-01234", Some(Region::NtscU)));
+
+    let id = game_id(epsxe(0xFFE0, b"CDROM:SCES_005.64;1\0")).unwrap();
+    assert_eq!((id.serial(), id.region()), ("SCES-00564", Some(Region::Pal)));
+
+    assert_eq!(game_id(epsxe(0x100, b"cdrom:\\PSX.EXE;1\0")), None);
+}
+
+#[test]
+fn kernel() {
+    let fixture = |tables: bool| {
+        let fixture = Fixture::new()
+            .module("ePSXe.exe", MODULE, 0x1000)
+            .sig(MODULE + 0x100, "C1 E1 10 8D 89")
+            .u32(MODULE + 0x105, WRAM as u32)
+            .data(WRAM, 0x200000);
+
+        if !tables {
+            return fixture
+        }
+
+        // Two events, and a process pointing to its thread
+        fixture
+            .u32(WRAM + 0x108, 0x80001000)
+            .u32(WRAM + 0x120, 0x80002000)
+            .u32(WRAM + 0x124, 2 * 0x1C)
+            .u32(WRAM + 0x1000, 0x80003000)
+            .u32(WRAM + 0x3000, 0x4000)
+            .u32(WRAM + 0x3000 + 0x88, 0x80010000)
+            .u32(WRAM + 0x2000 + 0x1C, 0xF0000003)
+            .u32(WRAM + 0x2000 + 0x1C + 4, 0x4000)
+            .u32(WRAM + 0x2000 + 0x1C + 8, 0x0020)
+            .u32(WRAM + 0x2000 + 0x1C + 0xC, 0x2000)
+    };
+
+    fixture(true).run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Epsxe);
+        assert!(emulator.update());
+
+        // The second event waits for the CD-ROM to complete a command, without any handler, and has been triggered
+        let event = emulator.event(1).unwrap();
+        assert_eq!((event.class, event.status, event.spec, event.mode), (0xF0000003, Event::READY, 0x0020, 0x2000));
+        assert_eq!(emulator.event(0).map(|event| event.status), Ok(Event::FREE));
+        assert_eq!(emulator.event(2), Err(Error::OutOfRange { address: 0x80002038, size: 0x1C }));
+
+        let thread = emulator.current_thread().unwrap();
+        assert_eq!((thread.status, thread.pc), (0x4000, 0x80010000));
+    });
+
+    fixture(false).run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Epsxe);
+        assert!(emulator.update());
+
+        assert_eq!(emulator.event(0), Err(Error::NullPointer { address: 0x120 }));
+        assert_eq!(emulator.current_thread(), Err(Error::NullPointer { address: 0x108 }));
+    })
+}
@@ -0,0 +1,28 @@
+use asr_emu_help::shared::Selection;
+
+const ENTRIES: [(&str, u8); 4] = [("a", 0), ("b", 1), ("b2", 1), ("c", 2)];
+
+fn order(selection: Selection<u8>) -> Vec<&'static str> {
+    selection.apply(&ENTRIES, |e| e.1).map(|e| e.0).collect()
+}
+
+#[test]
+fn default_order() {
+    assert_eq!(order(Selection::any()), ["a", "b", "b2", "c"]);
+}
+
+#[test]
+fn prefer_and_deny() {
+    assert_eq!(order(Selection::any().prefer(2).prefer(1)), ["c", "b", "b2", "a"]);
+    assert_eq!(order(Selection::any().deny(1)), ["a", "c"]);
+    assert_eq!(order(Selection::any().prefer(1).deny(1)), ["a", "c"]);
+}
+
+#[test]
+fn only() {
+    let selection = Selection::only(&[2, 0]);
+
+    assert_eq!(order(selection), ["c", "a"]);
+    assert!(!selection.allows(1));
+    assert!(selection.allows(0));
+}
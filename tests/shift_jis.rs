@@ -0,0 +1,29 @@
+#![cfg(feature = "shift-jis")]
+
+use asr_emu_help::shared::ShiftJis;
+
+#[test]
+fn decodes() {
+    // "ソニック ABC" followed by half-width katakana and padding
+    let bytes = b"\x83\x5C\x83\x6A\x83\x62\x83\x4E ABC\xB1\xB2\0\0garbage";
+    let text = ShiftJis::new(bytes);
+
+    assert_eq!(text.to_string(), "ソニック ABCｱｲ");
+    assert_eq!(text.as_bytes().len(), 14);
+}
+
+#[test]
+fn replaces_invalid_bytes() {
+    // A lead byte followed by an invalid trail byte keeps the trail byte, and a truncated character ends the text
+    assert_eq!(ShiftJis::new(b"\x83 A\xA0\x83").to_string(), "\u{FFFD} A\u{FFFD}\u{FFFD}");
+}
+
+#[test]
+fn decodes_into_buffer() {
+    let text = ShiftJis::new(b"\x82\xA0\x82\xA2");
+    let mut buf = [0; 5];
+
+    // Only the first character fits
+    assert_eq!(text.decode_into(&mut buf), "あ");
+    assert_eq!(format!("{text:?}"), "\"あい\"");
+}
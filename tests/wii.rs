@@ -0,0 +1,255 @@
+#![cfg(feature = "wii")]
+
+mod common;
+
+use core::cell::Cell;
+use asr::{Address, primitives::dynamic_endian::Endian, signature::Signature};
+use asr_emu_help::{wii::{Emulator, EmulatorType, Ptr}, shared::{InMemoryProcess, MemoryRegion, MemorySource}, Error};
+use bytemuck::CheckedBitPattern;
+use common::Fixture;
+
+const MEM_1: u64 = 0x7FFF0000;
+const MEM_2: u64 = 0x8FFF0000;
+
+#[test]
+fn dolphin() {
+    let fixture = Fixture::new()
+        .data(0x10000000, 0x2000000) // A MEM1-sized region without any MEM2 close to it
+        .data(MEM_1, 0x2000000)
+        .data(MEM_2, 0x4000000)
+        .bytes(MEM_1 + 0x10, &0x11223344u32.to_be_bytes())
+        .bytes(MEM_2 + 0x10, &0x55667788u32.to_be_bytes());
+
+    fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Dolphin);
+        assert!(emulator.update());
+        assert_eq!(emulator.read::<u32>(0x80000010), Ok(0x11223344));
+        assert_eq!(emulator.read::<u32>(0x90000010), Ok(0x55667788));
+    })
+}
+
+#[test]
+fn dolphin_uncached_mirrors() {
+    let fixture = Fixture::new()
+        .data(MEM_1, 0x2000000)
+        .data(MEM_2, 0x4000000)
+        .bytes(MEM_1 + 0x10, &0x11223344u32.to_be_bytes())
+        .bytes(MEM_2 + 0x10, &0x55667788u32.to_be_bytes());
+
+    fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Dolphin);
+        assert!(emulator.update());
+        assert_eq!(emulator.read::<u32>(0xC0000010), Ok(0x11223344));
+        assert_eq!(emulator.read::<u32>(0xD0000010), Ok(0x55667788));
+        assert_eq!(emulator.read_from_mem_1::<u32>(0xC0000010), Ok(0x11223344));
+        assert_eq!(emulator.read_from_mem_2::<u32>(0xD0000010), Ok(0x55667788));
+        assert_eq!(emulator.read_from_mem_2::<u32>(0xC0000010), Err(Error::OutOfRange { address: 0xC0000010, size: 4 }));
+        assert_eq!(emulator.read::<u32>(0xC1800000), Err(Error::OutOfRange { address: 0xC1800000, size: 4 }));
+        assert_eq!(emulator.read::<u32>(0xD4000000), Err(Error::OutOfRange { address: 0xD4000000, size: 4 }));
+    })
+}
+
+#[test]
+fn dolphin_without_mem_2() {
+    let fixture = Fixture::new().data(MEM_1, 0x2000000);
+
+    fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Dolphin);
+        assert!(!emulator.update());
+        assert!(emulator.ram().is_none());
+    })
+}
+
+#[test]
+fn dolphin_slices() {
+    let fixture = Fixture::new()
+        .data(MEM_1, 0x2000000)
+        .data(MEM_2, 0x4000000)
+        .bytes(MEM_1 + 0x17FFFF8, &[0, 1, 0, 2, 0, 3, 0, 4]);
+
+    fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Dolphin);
+        assert!(emulator.update());
+        assert_eq!(emulator.read_array::<u16, 4>(0x817FFFF8), Ok([1, 2, 3, 4]));
+        assert!(emulator.read_array::<u16, 5>(0x817FFFF8).is_err());
+    })
+}
+
+#[test]
+fn dolphin_pointer_paths() {
+    let fixture = Fixture::new()
+        .data(MEM_1, 0x2000000)
+        .data(MEM_2, 0x4000000)
+        .bytes(MEM_1 + 0x100, &0x90000200u32.to_be_bytes())
+        .bytes(MEM_2 + 0x208, &0x80000300u32.to_be_bytes())
+        .bytes(MEM_1 + 0x30C, &0x12345678u32.to_be_bytes());
+
+    fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Dolphin);
+        assert!(emulator.update());
+        assert_eq!(emulator.read_pointer_path::<u32>(0x80000100, &[0, 8, 0xC]), Ok(0x12345678));
+        assert_eq!(emulator.read_pointer_path::<u32>(0x80000100, &[0, 0, 0]), Err(Error::NullPointer { address: 0x90000200 }));
+    })
+}
+
+#[test]
+fn dolphin_typed_pointers() {
+    let fixture = Fixture::new()
+        .data(MEM_1, 0x2000000)
+        .data(MEM_2, 0x4000000)
+        .bytes(MEM_1 + 0x100, &0x90000200u32.to_be_bytes())
+        .bytes(MEM_1 + 0x104, &0xD0000200u32.to_be_bytes())
+        .bytes(MEM_1 + 0x108, &0x00000200u32.to_be_bytes())
+        .bytes(MEM_2 + 0x200, &[0, 1, 0, 2, 0, 3, 0, 4]);
+
+    fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Dolphin);
+        assert!(emulator.update());
+
+        let table = emulator.read::<Ptr<u16>>(0x80000100).unwrap();
+        assert_eq!(table, Ptr::new(0x90000200));
+        assert_eq!(table.index(2).read_from(&emulator), Ok(3));
+        assert_eq!(table.offset::<u32>(4).read_from(&emulator), Ok(0x00030004));
+        assert_eq!(Ptr::<u32>::null().read_from(&emulator), Err(Error::NullPointer { address: 0 }));
+
+        let uncached = emulator.read::<Ptr<u16>>(0x80000104).unwrap();
+        assert_eq!(uncached.index(2).read_from(&emulator), Ok(3));
+
+        // Raw offsets are only accepted by the emulator, not through pointers read from the game
+        let offset = emulator.read::<Ptr<u16>>(0x80000108).unwrap();
+        assert_eq!(offset.read_from(&emulator), Err(Error::OutOfRange { address: 0x200, size: 2 }));
+    })
+}
+
+#[test]
+fn memory_domains() {
+    let fixture = Fixture::new().data(MEM_1, 0x2000000).data(MEM_2, 0x4000000);
+
+    fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Dolphin);
+        assert_eq!(emulator.memory_domains().count(), 0);
+        assert!(emulator.update());
+
+        let domains: Vec<_> = emulator.memory_domains().collect();
+        assert_eq!(domains.iter().map(|domain| domain.name).collect::<Vec<_>>(), ["MEM1", "MEM2"]);
+        assert_eq!(domains[0].host_address, Address(MEM_1));
+        assert_eq!(domains[0].endian, Endian::Big);
+        assert_eq!(domains[1].to_host(0x90000010), Some(Address(MEM_2 + 0x10)));
+        assert_eq!(domains[1].to_host(0x94000000), None);
+    })
+}
+
+#[test]
+fn watches() {
+    let fixture = Fixture::new()
+        .data(MEM_1, 0x2000000)
+        .data(MEM_2, 0x4000000)
+        .bytes(MEM_1 + 0x10, &0x11223344u32.to_be_bytes());
+
+    fixture.run(|process| {
+        let mut emulator = Emulator::new(process, EmulatorType::Dolphin);
+        let value = emulator.watch::<u32>(0x80000010).unwrap();
+        let out_of_range = emulator.watch::<u16>(0x81800000).unwrap();
+        assert!(value.pair_from(&emulator).is_none());
+
+        assert!(emulator.update());
+        let pair = value.pair_from(&emulator).unwrap();
+        assert_eq!((pair.old, pair.current), (0x11223344, 0x11223344));
+        assert!(!pair.changed());
+        assert!(out_of_range.pair_from(&emulator).is_none());
+
+        assert!(emulator.update());
+        assert!(value.pair_from(&emulator).unwrap().unchanged());
+
+        emulator.unwatch(value);
+        assert!(value.pair_from(&emulator).is_none());
+    })
+}
+
+/// A process whose big endian `u32` at `address` can be changed while the emulator is running.
+struct WritableProcess<'a> {
+    process: InMemoryProcess<'a>,
+    address: u64,
+    value: &'a Cell<u32>,
+}
+
+impl MemorySource for WritableProcess<'_> {
+    fn is_open(&self) -> bool {
+        self.process.is_open()
+    }
+
+    fn module_address(&self, name: &str) -> Option<Address> {
+        self.process.module_address(name)
+    }
+
+    fn module_size(&self, name: &str) -> Option<u64> {
+        self.process.module_size(name)
+    }
+
+    fn memory_ranges(&self) -> impl Iterator<Item = MemoryRegion> + '_ {
+        self.process.memory_ranges()
+    }
+
+    fn read<T: CheckedBitPattern>(&self, address: Address) -> Option<T> {
+        let mut buf = vec![0; core::mem::size_of::<T>()];
+        self.read_into_buf(address, &mut buf)?;
+        bytemuck::checked::try_pod_read_unaligned(&buf).ok()
+    }
+
+    fn read_into_buf(&self, address: Address, buf: &mut [u8]) -> Option<()> {
+        self.process.read_into_buf(address, buf)?;
+
+        for (i, byte) in self.value.get().to_be_bytes().into_iter().enumerate() {
+            if let Some(offset) = (self.address + i as u64).checked_sub(address.0).filter(|&offset| offset < buf.len() as u64) {
+                buf[offset as usize] = byte;
+            }
+        }
+        Some(())
+    }
+
+    fn scan_signature<const N: usize>(&self, signature: &Signature<N>, address: Address, len: u64) -> Option<Address> {
+        self.process.scan_signature(signature, address, len)
+    }
+}
+
+#[test]
+fn watches_changing_values() {
+    let fixture = Fixture::new().data(MEM_1, 0x2000000).data(MEM_2, 0x4000000);
+
+    fixture.run(|process| {
+        let value = Cell::new(1);
+        let mut emulator = Emulator::new(WritableProcess { process, address: MEM_1 + 0x10, value: &value }, EmulatorType::Dolphin);
+        let watch = emulator.watch::<u32>(0x80000010).unwrap();
+
+        assert!(emulator.update());
+        assert!(watch.pair_from(&emulator).unwrap().unchanged());
+
+        value.set(5);
+        assert!(emulator.update());
+        let pair = watch.pair_from(&emulator).unwrap();
+        assert_eq!((pair.old, pair.current), (1, 5));
+        assert!(pair.changed() && pair.increased());
+
+        value.set(3);
+        assert!(emulator.update());
+        let pair = watch.pair_from(&emulator).unwrap();
+        assert_eq!((pair.old, pair.current), (5, 3));
+        assert!(pair.decreased() && pair.changed_from_to(&5, &3));
+
+        assert!(emulator.update());
+        assert!(watch.pair_from(&emulator).unwrap().unchanged());
+
+        // A new value registered in the same slot isn't reachable through the handle of the removed one
+        emulator.unwatch(watch);
+        let replacement = emulator.watch::<u32>(0x80000010).unwrap();
+        assert_ne!(watch, replacement);
+        assert!(emulator.update());
+        assert!(watch.pair_from(&emulator).is_none());
+
+        emulator.unwatch(watch);
+        assert_eq!(replacement.pair_from(&emulator).map(|pair| pair.current), Some(3));
+
+        emulator.clear_watches();
+        assert!(replacement.pair_from(&emulator).is_none());
+    })
+}